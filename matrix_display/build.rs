@@ -0,0 +1,353 @@
+//! Converts the images in `images/` into constants the effects can draw without decoding
+//! anything at runtime, written to `assets.rs` in `OUT_DIR` and included by `src/assets.rs`.
+//!
+//! BMPs are always supported; PNGs and GIFs need the `png` and `gif` features. Every frame of an
+//! animated GIF is kept, with its delay. Transparent pixels become black, i.e. off. Images are
+//! checked against the matrix size, 16x16 unless `MATRIX_ROWS` and `MATRIX_COLS` say otherwise.
+//! Each image is stored however takes the least flash, or as `MATRIX_IMAGE_ENCODING` (`raw`,
+//! `palette` or `rle`) says.
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use embedded_graphics_core::{
+    geometry::OriginDimensions,
+    pixelcolor::{Rgb888, RgbColor},
+};
+use tinybmp::Bmp;
+
+/// A decoded image, as red, green, blue for each pixel in row-major order
+struct Picture {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Encoding {
+    Raw,
+    Palette,
+    Rle,
+}
+
+fn main() {
+    let images = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("../images");
+    println!("cargo:rerun-if-changed={}", images.display());
+    let rows = matrix_size("MATRIX_ROWS");
+    let cols = matrix_size("MATRIX_COLS");
+    println!("cargo:rerun-if-env-changed=MATRIX_IMAGE_ENCODING");
+    let encoding = env::var("MATRIX_IMAGE_ENCODING")
+        .ok()
+        .map(|e| match e.as_str() {
+            "raw" => Encoding::Raw,
+            "palette" => Encoding::Palette,
+            "rle" => Encoding::Rle,
+            _ => panic!("MATRIX_IMAGE_ENCODING should be raw, palette or rle, not {e:?}"),
+        });
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&images)
+        .unwrap_or_else(|e| panic!("can't read {}: {e}", images.display()))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    let mut out = String::new();
+    let (mut stills, mut animations) = (Vec::new(), Vec::new());
+    for path in paths {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        let frames = match extension.as_deref() {
+            Some("bmp") => vec![(read_bmp(&path), 0)],
+            Some("png") => vec![(read_png(&path), 0)],
+            Some("gif") => read_gif(&path),
+            _ => continue,
+        };
+        for (picture, _) in &frames {
+            if picture.height > rows || picture.width > cols {
+                panic!(
+                    "{} is {}x{} but the matrix is only {cols}x{rows}",
+                    path.display(),
+                    picture.width,
+                    picture.height
+                );
+            }
+        }
+
+        let name = path.file_stem().unwrap().to_str().unwrap().to_owned();
+        let ident = identifier(&name);
+        writeln!(
+            out,
+            "/// Converted from `{}`",
+            path.file_name().unwrap().to_str().unwrap()
+        )
+        .unwrap();
+        if extension.as_deref() == Some("gif") {
+            writeln!(out, "pub const {ident}: &[Keyframe] = &[").unwrap();
+            for (picture, duration_ms) in &frames {
+                writeln!(
+                    out,
+                    "    Keyframe {{ image: &{}, duration_ms: {duration_ms} }},",
+                    image(picture, encoding)
+                )
+                .unwrap();
+            }
+            writeln!(out, "];").unwrap();
+            animations.push((name, ident));
+        } else {
+            writeln!(
+                out,
+                "pub const {ident}: Image = {};",
+                image(&frames[0].0, encoding)
+            )
+            .unwrap();
+            stills.push((name, ident));
+        }
+    }
+
+    writeln!(out, "/// Every still image, by file name").unwrap();
+    writeln!(out, "pub const IMAGES: &[(&str, &Image)] = &[").unwrap();
+    for (name, ident) in &stills {
+        writeln!(out, "    ({name:?}, &{ident}),").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out, "/// Every animation, by file name").unwrap();
+    writeln!(out, "pub const ANIMATIONS: &[(&str, &[Keyframe])] = &[").unwrap();
+    for (name, ident) in &animations {
+        writeln!(out, "    ({name:?}, {ident}),").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("assets.rs");
+    fs::write(dest, out).unwrap();
+}
+
+fn matrix_size(var: &str) -> usize {
+    println!("cargo:rerun-if-env-changed={var}");
+    env::var(var)
+        .map(|s| {
+            s.parse()
+                .unwrap_or_else(|_| panic!("{var} should be a number"))
+        })
+        .unwrap_or(16)
+}
+
+/// `cake_1` becomes `CAKE_1`
+fn identifier(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("IMAGE_{ident}")
+    } else {
+        ident
+    }
+}
+
+fn read_bmp(path: &Path) -> Picture {
+    let bytes = fs::read(path).unwrap();
+    let bmp = Bmp::<Rgb888>::from_slice(&bytes)
+        .unwrap_or_else(|e| panic!("{} isn't a BMP tinybmp can read: {e:?}", path.display()));
+    let size = bmp.size();
+    let (width, height) = (size.width as usize, size.height as usize);
+    let mut pixels = vec![[0; 3]; width * height];
+    for p in bmp.pixels() {
+        pixels[p.0.y as usize * width + p.0.x as usize] = [p.1.r(), p.1.g(), p.1.b()];
+    }
+    Picture {
+        width,
+        height,
+        pixels,
+    }
+}
+
+#[cfg(feature = "png")]
+fn read_png(path: &Path) -> Picture {
+    let fail = |e: png::DecodingError| -> ! { panic!("can't read {}: {e}", path.display()) };
+    let mut decoder = png::Decoder::new(fs::File::open(path).unwrap());
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().unwrap_or_else(|e| fail(e));
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).unwrap_or_else(|e| fail(e));
+    let bytes = &buffer[..info.buffer_size()];
+    let pixels = match info.color_type {
+        png::ColorType::Rgb => bytes.chunks(3).map(|p| [p[0], p[1], p[2]]).collect(),
+        png::ColorType::Rgba => bytes
+            .chunks(4)
+            .map(|p| opaque(p[0], p[1], p[2], p[3]))
+            .collect(),
+        png::ColorType::Grayscale => bytes.iter().map(|&v| [v; 3]).collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks(2)
+            .map(|p| opaque(p[0], p[0], p[0], p[1]))
+            .collect(),
+        // EXPAND turns these into RGB
+        png::ColorType::Indexed => unreachable!(),
+    };
+    Picture {
+        width: info.width as usize,
+        height: info.height as usize,
+        pixels,
+    }
+}
+
+#[cfg(not(feature = "png"))]
+fn read_png(path: &Path) -> Picture {
+    panic!("{} is a PNG, which needs the `png` feature", path.display())
+}
+
+/// Every frame of a GIF, drawn over the ones before as a browser would, with its delay in ms
+#[cfg(feature = "gif")]
+fn read_gif(path: &Path) -> Vec<(Picture, u16)> {
+    /// How long a frame is shown when it doesn't say, as browsers do
+    const DEFAULT_DELAY_MS: u16 = 100;
+    let fail = |e: gif::DecodingError| -> ! { panic!("can't read {}: {e}", path.display()) };
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options
+        .read_info(fs::File::open(path).unwrap())
+        .unwrap_or_else(|e| fail(e));
+    let (width, height) = (decoder.width() as usize, decoder.height() as usize);
+    let mut canvas = vec![[0; 4]; width * height];
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap_or_else(|e| fail(e)) {
+        let previous = canvas.clone();
+        let (left, top) = (frame.left as usize, frame.top as usize);
+        let region =
+            (0..frame.height as usize).flat_map(|y| (0..frame.width as usize).map(move |x| (y, x)));
+        let region: Vec<_> = region
+            .filter(|&(y, x)| top + y < height && left + x < width)
+            .collect();
+        for &(y, x) in &region {
+            let i = (y * frame.width as usize + x) * 4;
+            let p = &frame.buffer[i..i + 4];
+            if p[3] != 0 {
+                canvas[(top + y) * width + left + x] = [p[0], p[1], p[2], p[3]];
+            }
+        }
+        let delay_ms = match frame.delay {
+            0 => DEFAULT_DELAY_MS,
+            centiseconds => centiseconds.saturating_mul(10),
+        };
+        frames.push((
+            Picture {
+                width,
+                height,
+                pixels: canvas
+                    .iter()
+                    .map(|p| opaque(p[0], p[1], p[2], p[3]))
+                    .collect(),
+            },
+            delay_ms,
+        ));
+        match frame.dispose {
+            gif::DisposalMethod::Background => {
+                for &(y, x) in &region {
+                    canvas[(top + y) * width + left + x] = [0; 4];
+                }
+            }
+            gif::DisposalMethod::Previous => canvas = previous,
+            gif::DisposalMethod::Any | gif::DisposalMethod::Keep => (),
+        }
+    }
+    frames
+}
+
+#[cfg(not(feature = "gif"))]
+fn read_gif(path: &Path) -> Vec<(Picture, u16)> {
+    panic!("{} is a GIF, which needs the `gif` feature", path.display())
+}
+
+/// Only fully transparent pixels are treated as transparent
+#[cfg_attr(not(any(feature = "png", feature = "gif")), allow(dead_code))]
+fn opaque(r: u8, g: u8, b: u8, a: u8) -> [u8; 3] {
+    if a == 0 {
+        [0; 3]
+    } else {
+        [r, g, b]
+    }
+}
+
+fn colour([r, g, b]: [u8; 3]) -> String {
+    format!("RGB8::new({r}, {g}, {b})")
+}
+
+fn list(items: impl IntoIterator<Item = String>) -> String {
+    items.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+/// The picture as an `Image` expression, stored as `encoding` or whichever way is smallest
+fn image(picture: &Picture, encoding: Option<Encoding>) -> String {
+    let mut palette = Vec::new();
+    let mut lookup = HashMap::new();
+    let indices: Vec<usize> = picture
+        .pixels
+        .iter()
+        .map(|&p| {
+            *lookup.entry(p).or_insert_with(|| {
+                palette.push(p);
+                palette.len() - 1
+            })
+        })
+        .collect();
+    let mut runs: Vec<(u8, [u8; 3])> = Vec::new();
+    for &p in &picture.pixels {
+        match runs.last_mut() {
+            Some((length, colour)) if *colour == p && *length < u8::MAX => *length += 1,
+            _ => runs.push((1, p)),
+        }
+    }
+
+    // what each takes in flash, with an RGB8 being four bytes
+    let mut sizes = vec![
+        (Encoding::Raw, picture.pixels.len() * 4),
+        (Encoding::Rle, runs.len() * 5),
+    ];
+    if palette.len() <= 256 {
+        sizes.push((Encoding::Palette, palette.len() * 4 + indices.len()));
+    }
+    let encoding =
+        encoding.unwrap_or_else(|| sizes.iter().min_by_key(|(_, size)| *size).unwrap().0);
+
+    let pixels = match encoding {
+        Encoding::Raw => format!(
+            "Pixels::Raw(&[{}])",
+            list(picture.pixels.iter().map(|&p| colour(p)))
+        ),
+        Encoding::Palette if palette.len() > 256 => {
+            panic!(
+                "an image has {} colours, too many for a palette",
+                palette.len()
+            )
+        }
+        Encoding::Palette => format!(
+            "Pixels::Palette {{ colours: &[{}], indices: &[{}] }}",
+            list(palette.into_iter().map(colour)),
+            list(indices.iter().map(|i| i.to_string()))
+        ),
+        Encoding::Rle => format!(
+            "Pixels::Rle(&[{}])",
+            list(
+                runs.into_iter()
+                    .map(|(n, p)| format!("({n}, {})", colour(p)))
+            )
+        ),
+    };
+    format!(
+        "Image {{ width: {}, height: {}, pixels: {pixels} }}",
+        picture.width, picture.height
+    )
+}
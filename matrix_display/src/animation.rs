@@ -0,0 +1,177 @@
+//! Plays a sequence of images converted at build time. Each frame is unpacked from its image as
+//! it is drawn, so an animation only holds references to the images in flash.
+
+use heapless::Vec;
+
+use super::{
+    frame_buffer::FrameBuffer,
+    image::Image,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamValue, Tunable},
+};
+
+/// One image of an animation and how long to show it for
+#[derive(Debug, Copy, Clone)]
+pub struct Keyframe {
+    /// Usually one of the [`assets`](super::assets)
+    pub image: &'static Image,
+    pub duration_ms: u16,
+}
+
+/// What happens after the last frame
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Start again from the first frame
+    Loop,
+    /// Play backwards to the first frame, then forwards again
+    PingPong,
+    /// Stay on the last frame
+    Once,
+}
+
+const MODES: &[&str] = &["loop", "ping-pong", "once"];
+
+impl PlayMode {
+    fn from_index(i: u8) -> Option<Self> {
+        [Self::Loop, Self::PingPong, Self::Once]
+            .get(i as usize)
+            .copied()
+    }
+}
+
+#[derive(Debug)]
+pub enum AnimationError {
+    /// There are more keyframes than the animation has room for
+    TooManyFrames,
+    NoFrames,
+}
+
+/// Shows each keyframe in turn for its duration, as its own display or as part of another
+pub struct Animation<const N: usize> {
+    frames: Vec<Keyframe, N>,
+    mode: PlayMode,
+    /// How much time passes between updates, the effect's frame spacing
+    tick_ms: u16,
+    index: usize,
+    forwards: bool,
+    elapsed_ms: u32,
+}
+
+impl<const N: usize> Animation<N> {
+    pub fn new(
+        keyframes: &[Keyframe],
+        mode: PlayMode,
+        tick_ms: u16,
+    ) -> Result<Self, AnimationError> {
+        if keyframes.is_empty() {
+            return Err(AnimationError::NoFrames);
+        }
+        let frames = Vec::from_slice(keyframes).map_err(|_| AnimationError::TooManyFrames)?;
+        Ok(Self {
+            frames,
+            mode,
+            tick_ms,
+            index: 0,
+            forwards: true,
+            elapsed_ms: 0,
+        })
+    }
+
+    /// The index of the frame being shown
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn mode(&self) -> PlayMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: PlayMode) {
+        self.mode = mode;
+        self.forwards = true;
+    }
+
+    /// Go back to the first frame
+    pub fn restart(&mut self) {
+        self.index = 0;
+        self.forwards = true;
+        self.elapsed_ms = 0;
+    }
+
+    /// Draw the current frame into the top left of the buffer, cropping whatever doesn't fit and
+    /// clearing whatever the image doesn't cover
+    pub fn draw<const ROWS: usize, const COLS: usize>(
+        &self,
+        buffer: &mut impl FrameBuffer<ROWS, COLS>,
+    ) {
+        buffer.clear();
+        self.frames[self.index].image.draw(buffer);
+    }
+
+    /// Let a tick pass, moving on as many frames as have run out of time
+    pub fn tick(&mut self) {
+        self.elapsed_ms += self.tick_ms as u32;
+        loop {
+            let duration = self.frames[self.index].duration_ms as u32;
+            if self.elapsed_ms < duration {
+                break;
+            }
+            self.elapsed_ms -= duration;
+            if !self.advance() {
+                self.elapsed_ms = 0;
+                break;
+            }
+        }
+    }
+
+    /// Move to the next frame, returning false if the animation has finished
+    fn advance(&mut self) -> bool {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            _ if last == 0 => return false,
+            PlayMode::Loop => self.index = (self.index + 1) % self.frames.len(),
+            PlayMode::Once if self.index == last => return false,
+            PlayMode::Once => self.index += 1,
+            PlayMode::PingPong => {
+                if (self.forwards && self.index == last) || (!self.forwards && self.index == 0) {
+                    self.forwards = !self.forwards;
+                }
+                self.index = if self.forwards {
+                    self.index + 1
+                } else {
+                    self.index - 1
+                };
+            }
+        }
+        true
+    }
+}
+
+impl<const N: usize> Tunable for Animation<N> {
+    const PARAMS: &'static [ParamSpec] = &[ParamSpec {
+        name: "mode",
+        kind: ParamKind::Enum(MODES),
+        default: ParamValue::Enum(0),
+    }];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        (index == 0).then_some(ParamValue::Enum(self.mode as u8))
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        if let (0, ParamValue::Enum(i)) = (index, value) {
+            if let Some(mode) = PlayMode::from_index(i) {
+                self.set_mode(mode);
+            }
+        }
+    }
+}
+
+impl<const N: usize, const ROWS: usize, const COLS: usize> MatrixDisplayer<ROWS, COLS>
+    for Animation<N>
+{
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        self.draw(buffer);
+        self.tick();
+    }
+}
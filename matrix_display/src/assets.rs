@@ -0,0 +1,20 @@
+//! Every image in `images/`, converted by the build script. Each file becomes a constant named
+//! after it, so `cake_1.bmp` is `CAKE_1`; animated GIFs become a list of [`Keyframe`]s instead.
+
+use super::{
+    animation::Keyframe,
+    image::{Image, Pixels},
+    rgb8::RGB8,
+};
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+/// Look up a still image by its file name, without the extension
+pub fn image(name: &str) -> Option<&'static Image> {
+    IMAGES.iter().find(|(n, _)| *n == name).map(|(_, i)| *i)
+}
+
+/// Look up an animated GIF by its file name, without the extension
+pub fn animation(name: &str) -> Option<&'static [Keyframe]> {
+    ANIMATIONS.iter().find(|(n, _)| *n == name).map(|(_, a)| *a)
+}
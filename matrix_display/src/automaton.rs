@@ -0,0 +1,545 @@
+//! A cellular automaton engine running a choice of rules on a wrap-around board. Each cell holds a
+//! state number, 0 being empty.
+//!
+//! Rules are written as:
+//! - `B{counts}/S{counts}` for Life-like rules, e.g. `B36/S23` for HighLife, where a cell is born
+//!   with one of the birth counts of live neighbours and survives with one of the survival counts
+//! - `B{counts}/S{counts}/C{states}` for Generations rules, where cells that don't survive take
+//!   `states - 2` more generations to die, e.g. `B2/S/C3` for Brian's Brain
+//! - either of those followed by `V` to count only the four orthogonal neighbours
+//! - `wireworld`, `ant` for Langton's ant, or `rule{n}` for a 1D elementary rule scrolling up
+//!   the board
+//!
+//! or as one of the preset names in [`PRESETS`]. The automaton's `rule` parameter takes any of
+//! these as text, and its `neighbourhood` and `states` parameters change those parts of a
+//! Life-like or Generations rule.
+
+use core::{fmt, str::FromStr};
+
+use rand::Rng;
+
+use super::{
+    frame_buffer::FrameBuffer,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamText, ParamValue, Tunable},
+    rgb8::RGB8,
+    rng::EffectRng,
+};
+
+/// The most states a Generations rule can have
+pub const MAX_STATES: u8 = 16;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// All eight surrounding cells
+    Moore,
+    /// Only the four cells above, below and to the sides
+    VonNeumann,
+}
+
+/// The options of the automaton's `neighbourhood` parameter, in the order of [`Neighbourhood`]
+const NEIGHBOURHOODS: &[&str] = &["moore", "von-neumann"];
+
+impl Neighbourhood {
+    fn from_index(i: u8) -> Option<Self> {
+        [Self::Moore, Self::VonNeumann].get(i as usize).copied()
+    }
+
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Moore => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+            Self::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        }
+    }
+}
+
+/// A rule that only looks at how many neighbours are alive
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Totalistic {
+    /// Bit `n` is set if a cell is born with `n` live neighbours
+    pub birth: u16,
+    /// Bit `n` is set if a live cell survives with `n` live neighbours
+    pub survive: u16,
+    /// 2 for Life-like rules, more for Generations rules
+    pub states: u8,
+    pub neighbourhood: Neighbourhood,
+}
+
+impl Totalistic {
+    /// The same rule counting a different neighbourhood, dropping any counts it can't reach
+    pub fn with_neighbourhood(self, neighbourhood: Neighbourhood) -> Self {
+        let reachable = (1 << (neighbourhood.offsets().len() + 1)) - 1;
+        Self {
+            birth: self.birth & reachable,
+            survive: self.survive & reachable,
+            neighbourhood,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rule {
+    Totalistic(Totalistic),
+    /// Empty, electron head, electron tail and conductor
+    Wireworld,
+    LangtonsAnt,
+    /// One of the 256 elementary rules, by its Wolfram number
+    Elementary(u8),
+}
+
+const fn counts(digits: &[u8]) -> u16 {
+    let mut bits = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        bits |= 1 << digits[i];
+        i += 1;
+    }
+    bits
+}
+
+const fn totalistic(birth: &[u8], survive: &[u8], states: u8) -> Rule {
+    Rule::Totalistic(Totalistic {
+        birth: counts(birth),
+        survive: counts(survive),
+        states,
+        neighbourhood: Neighbourhood::Moore,
+    })
+}
+
+impl Rule {
+    pub const LIFE: Self = totalistic(&[3], &[2, 3], 2);
+    pub const HIGHLIFE: Self = totalistic(&[3, 6], &[2, 3], 2);
+    pub const BRIANS_BRAIN: Self = totalistic(&[2], &[], 3);
+    pub const SEEDS: Self = totalistic(&[2], &[], 2);
+    pub const DAY_AND_NIGHT: Self = totalistic(&[3, 6, 7, 8], &[3, 4, 6, 7, 8], 2);
+    pub const DEFAULT: Self = Self::BRIANS_BRAIN;
+}
+
+/// The named rules
+pub const PRESETS: &[(&str, Rule)] = &[
+    ("brians-brain", Rule::BRIANS_BRAIN),
+    ("life", Rule::LIFE),
+    ("highlife", Rule::HIGHLIFE),
+    ("seeds", Rule::SEEDS),
+    ("day-and-night", Rule::DAY_AND_NIGHT),
+    ("wireworld", Rule::Wireworld),
+    ("ant", Rule::LangtonsAnt),
+    ("rule30", Rule::Elementary(30)),
+    ("rule110", Rule::Elementary(110)),
+];
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |f: &mut fmt::Formatter<'_>, bits: u16| {
+            (0..=8)
+                .filter(|n| bits & 1 << n != 0)
+                .try_for_each(|n| write!(f, "{}", n))
+        };
+        match self {
+            Self::Totalistic(t) => {
+                f.write_str("B")?;
+                digits(f, t.birth)?;
+                f.write_str("/S")?;
+                digits(f, t.survive)?;
+                if t.states > 2 {
+                    write!(f, "/C{}", t.states)?;
+                }
+                if t.neighbourhood == Neighbourhood::VonNeumann {
+                    f.write_str("V")?;
+                }
+                Ok(())
+            }
+            Self::Wireworld => f.write_str("wireworld"),
+            Self::LangtonsAnt => f.write_str("ant"),
+            Self::Elementary(n) => write!(f, "rule{}", n),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, rule)) = PRESETS.iter().find(|(name, _)| *name == s) {
+            return Ok(*rule);
+        }
+        if let Some(n) = s.strip_prefix("rule") {
+            return n.parse().map(Self::Elementary).map_err(|_| ());
+        }
+
+        let (s, neighbourhood) = match s.strip_suffix(['V', 'v']) {
+            Some(s) => (s, Neighbourhood::VonNeumann),
+            None => (s, Neighbourhood::Moore),
+        };
+        let max_count = neighbourhood.offsets().len() as u32;
+        let digits = |part: &str| {
+            part.chars().try_fold(0u16, |bits, c| match c.to_digit(10) {
+                Some(n) if n <= max_count => Ok(bits | 1 << n),
+                _ => Err(()),
+            })
+        };
+        let mut parts = s.split('/');
+        let birth = parts.next().and_then(|p| p.strip_prefix(['B', 'b']));
+        let survive = parts.next().and_then(|p| p.strip_prefix(['S', 's']));
+        let (Some(birth), Some(survive)) = (birth, survive) else {
+            return Err(());
+        };
+        let states = match parts.next() {
+            Some(p) => p
+                .strip_prefix(['C', 'c'])
+                .ok_or(())?
+                .parse()
+                .map_err(|_| ())?,
+            None => 2,
+        };
+        if parts.next().is_some() || !(2..=MAX_STATES).contains(&states) {
+            return Err(());
+        }
+        Ok(Self::Totalistic(Totalistic {
+            birth: digits(birth)?,
+            survive: digits(survive)?,
+            states,
+            neighbourhood,
+        }))
+    }
+}
+
+/// Where Langton's ant is and which way it faces, 0 being up and counting clockwise
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ant {
+    pub row: usize,
+    pub col: usize,
+    pub heading: u8,
+}
+
+/// How many moves the ant makes each frame, so its patterns build up at a watchable pace
+const ANT_STEPS: usize = 4;
+
+/// The colour of Wireworld's conductor
+const COPPER: RGB8 = RGB8::new(90, 40, 0);
+
+pub struct Automaton<const ROWS: usize, const COLS: usize> {
+    cells: [[u8; COLS]; ROWS],
+    rule: Rule,
+    ant: Ant,
+    /// The chance of each cell starting alive, for totalistic rules
+    density: f32,
+    /// Live cells, electron heads and the ant's trail
+    primary: RGB8,
+    /// Dying cells, electron tails and the ant itself
+    secondary: RGB8,
+    rng: EffectRng,
+}
+
+impl<const ROWS: usize, const COLS: usize> Automaton<ROWS, COLS> {
+    pub fn new(seed: u64) -> Self {
+        Self::with_rule(seed, Rule::DEFAULT)
+    }
+
+    pub fn with_rule(seed: u64, rule: Rule) -> Self {
+        let mut automaton = Self {
+            cells: [[0; COLS]; ROWS],
+            rule,
+            ant: Ant {
+                row: ROWS / 2,
+                col: COLS / 2,
+                heading: 0,
+            },
+            density: 0.3,
+            primary: RGB8::new(180, 220, 255),
+            secondary: RGB8::new(0, 40, 160),
+            rng: EffectRng::new(seed),
+        };
+        automaton.reseed();
+        automaton
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+        self.reseed();
+    }
+
+    pub fn cells(&self) -> &[[u8; COLS]; ROWS] {
+        &self.cells
+    }
+
+    pub fn ant(&self) -> Ant {
+        self.ant
+    }
+
+    /// Start the board again: random cells for totalistic rules, a loop of wire with an electron
+    /// on it for Wireworld, an empty board for the ant and a single cell for elementary rules
+    pub fn reseed(&mut self) {
+        self.cells = [[0; COLS]; ROWS];
+        match self.rule {
+            Rule::Totalistic(_) => {
+                let density = self.density as f64;
+                for row in self.cells.iter_mut() {
+                    for cell in row.iter_mut() {
+                        *cell = self.rng.gen_bool(density) as u8;
+                    }
+                }
+            }
+            Rule::Wireworld => {
+                // a loop just inside the edges with a wire across the middle, so electrons split
+                // where they meet it
+                let (bottom, right) = (ROWS - 2, COLS - 2);
+                for col in 1..=right {
+                    self.cells[1][col] = 3;
+                    self.cells[bottom][col] = 3;
+                    self.cells[ROWS / 2][col] = 3;
+                }
+                for row in 1..=bottom {
+                    self.cells[row][1] = 3;
+                    self.cells[row][right] = 3;
+                }
+                let col = self.rng.gen_range(2..right);
+                self.cells[1][col] = 1;
+                self.cells[1][col - 1] = 2;
+            }
+            Rule::LangtonsAnt => {
+                self.ant = Ant {
+                    row: ROWS / 2,
+                    col: COLS / 2,
+                    heading: 0,
+                };
+            }
+            Rule::Elementary(_) => self.cells[ROWS - 1][COLS / 2] = 1,
+        }
+    }
+
+    fn neighbour(&self, row: usize, col: usize, (dr, dc): (isize, isize)) -> u8 {
+        let row = (row as isize + dr).rem_euclid(ROWS as isize) as usize;
+        let col = (col as isize + dc).rem_euclid(COLS as isize) as usize;
+        self.cells[row][col]
+    }
+
+    /// How many of the cell's neighbours are in `state`
+    fn count(&self, row: usize, col: usize, neighbourhood: Neighbourhood, state: u8) -> usize {
+        neighbourhood
+            .offsets()
+            .iter()
+            .filter(|&&offset| self.neighbour(row, col, offset) == state)
+            .count()
+    }
+
+    /// Advance one generation, reseeding if every cell has died
+    pub fn step(&mut self) {
+        match self.rule {
+            Rule::Totalistic(rule) => self.step_totalistic(rule),
+            Rule::Wireworld => self.step_wireworld(),
+            Rule::LangtonsAnt => (0..ANT_STEPS).for_each(|_| self.step_ant()),
+            Rule::Elementary(n) => self.step_elementary(n),
+        }
+        if self.rule != Rule::LangtonsAnt && self.cells.iter().flatten().all(|&c| c == 0) {
+            self.reseed();
+        }
+    }
+
+    fn step_totalistic(&mut self, rule: Totalistic) {
+        let mut next = [[0; COLS]; ROWS];
+        for (row, col) in <Self as MatrixDisplayer<ROWS, COLS>>::iterate(self) {
+            let live = self.count(row, col, rule.neighbourhood, 1);
+            next[row][col] = match self.cells[row][col] {
+                0 => (rule.birth & 1 << live != 0) as u8,
+                1 if rule.survive & 1 << live != 0 => 1,
+                // dying, or dead if there are no dying states
+                state => (state + 1) % rule.states,
+            };
+        }
+        self.cells = next;
+    }
+
+    fn step_wireworld(&mut self) {
+        let mut next = [[0; COLS]; ROWS];
+        for (row, col) in <Self as MatrixDisplayer<ROWS, COLS>>::iterate(self) {
+            next[row][col] = match self.cells[row][col] {
+                0 => 0,
+                1 => 2,
+                2 => 3,
+                _ => match self.count(row, col, Neighbourhood::Moore, 1) {
+                    1 | 2 => 1,
+                    _ => 3,
+                },
+            };
+        }
+        self.cells = next;
+    }
+
+    /// Turn right on an empty cell and left on a full one, flip it and move forward
+    fn step_ant(&mut self) {
+        let Ant { row, col, heading } = self.ant;
+        let cell = &mut self.cells[row][col];
+        let heading = (if *cell == 0 { heading + 1 } else { heading + 3 }) % 4;
+        *cell ^= 1;
+        let (dr, dc) = [(-1, 0), (0, 1), (1, 0), (0, -1)][heading as usize];
+        self.ant = Ant {
+            row: (row as isize + dr).rem_euclid(ROWS as isize) as usize,
+            col: (col as isize + dc).rem_euclid(COLS as isize) as usize,
+            heading,
+        };
+    }
+
+    /// Scroll the board up and work out a new bottom row from the one above it
+    fn step_elementary(&mut self, rule: u8) {
+        self.cells.rotate_left(1);
+        let last = self.cells[ROWS - 2];
+        for col in 0..COLS {
+            let left = last[(col + COLS - 1) % COLS];
+            let right = last[(col + 1) % COLS];
+            let pattern = left << 2 | last[col] << 1 | right;
+            self.cells[ROWS - 1][col] = rule >> pattern & 1;
+        }
+    }
+
+    fn colour(&self, state: u8) -> RGB8 {
+        match (self.rule, state) {
+            (_, 0) => RGB8::default(),
+            (Rule::Wireworld, 1) => self.primary,
+            (Rule::Wireworld, 2) => self.secondary,
+            (Rule::Wireworld, _) => COPPER,
+            // dying cells fade out as they get closer to empty
+            (Rule::Totalistic(rule), state) if state > 1 => {
+                let (left, steps) = ((rule.states - state) as u16, (rule.states - 2) as u16);
+                let fade = |c: u8| (c as u16 * left / steps) as u8;
+                let c = self.secondary;
+                RGB8::new(fade(c.r), fade(c.g), fade(c.b))
+            }
+            _ => self.primary,
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Tunable for Automaton<ROWS, COLS> {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "rule",
+            kind: ParamKind::Text,
+            // Rule::DEFAULT, written out
+            default: ParamValue::Text(ParamText::new("B2/S/C3").unwrap()),
+        },
+        ParamSpec {
+            name: "neighbourhood",
+            kind: ParamKind::Enum(NEIGHBOURHOODS),
+            default: ParamValue::Enum(0),
+        },
+        ParamSpec {
+            name: "states",
+            kind: ParamKind::Int {
+                min: 2,
+                max: MAX_STATES as i32,
+            },
+            default: ParamValue::Int(3),
+        },
+        ParamSpec {
+            name: "density",
+            kind: ParamKind::Float {
+                min: 0.05,
+                max: 0.9,
+            },
+            default: ParamValue::Float(0.3),
+        },
+        ParamSpec {
+            name: "primary",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(RGB8::new(180, 220, 255)),
+        },
+        ParamSpec {
+            name: "secondary",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(RGB8::new(0, 40, 160)),
+        },
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            // any rule fits, the longest being B012345678/S012345678/C16V
+            0 => ParamValue::Text(ParamText::from_display(self.rule)?),
+            // the other rules have their own neighbourhoods and states, so show the defaults
+            1 => match self.rule {
+                Rule::Totalistic(t) => ParamValue::Enum(t.neighbourhood as u8),
+                _ => ParamValue::Enum(0),
+            },
+            2 => match self.rule {
+                Rule::Totalistic(t) => ParamValue::Int(t.states as i32),
+                _ => ParamValue::Int(2),
+            },
+            3 => ParamValue::Float(self.density),
+            4 => ParamValue::Colour(self.primary),
+            5 => ParamValue::Colour(self.secondary),
+            _ => return None,
+        })
+    }
+
+    fn check_param(&self, index: usize, value: ParamValue) -> bool {
+        match (index, value) {
+            (0, ParamValue::Text(rule)) => rule.as_str().parse::<Rule>().is_ok(),
+            _ => true,
+        }
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Text(rule)) => {
+                if let Ok(rule) = rule.as_str().parse() {
+                    self.set_rule(rule);
+                }
+            }
+            (1, ParamValue::Enum(i)) => {
+                if let (Rule::Totalistic(t), Some(neighbourhood)) =
+                    (self.rule, Neighbourhood::from_index(i))
+                {
+                    self.set_rule(Rule::Totalistic(t.with_neighbourhood(neighbourhood)));
+                }
+            }
+            (2, ParamValue::Int(states)) => {
+                if let Rule::Totalistic(t) = self.rule {
+                    let states = states as u8;
+                    self.set_rule(Rule::Totalistic(Totalistic { states, ..t }));
+                }
+            }
+            (3, ParamValue::Float(density)) => {
+                self.density = density;
+                self.reseed();
+            }
+            (4, ParamValue::Colour(primary)) => self.primary = primary,
+            (5, ParamValue::Colour(secondary)) => self.secondary = secondary,
+            _ => (),
+        }
+    }
+}
+
+/// Draws whatever part of the board overlaps the buffer
+impl<const R: usize, const C: usize, const ROWS: usize, const COLS: usize>
+    MatrixDisplayer<ROWS, COLS> for Automaton<R, C>
+{
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        for row in 0..ROWS.min(R) {
+            for col in 0..COLS.min(C) {
+                buffer[(row, col)] = self.colour(self.cells[row][col]);
+            }
+        }
+        if self.rule == Rule::LangtonsAnt && self.ant.row < ROWS && self.ant.col < COLS {
+            buffer[(self.ant.row, self.ant.col)] = self.secondary;
+        }
+        self.step();
+    }
+}
@@ -0,0 +1,163 @@
+use heapless::Vec;
+use rand::Rng;
+
+use super::{
+    animation::{Animation, Keyframe, PlayMode},
+    assets,
+    frame_buffer::FrameBuffer,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamValue, Tunable},
+    rgb8::RGB8,
+    rng::EffectRng,
+};
+
+/// The candle flickering, to be played at Cake's 50 ms frame spacing
+const FLAME: [Keyframe; 6] = [
+    Keyframe {
+        image: &assets::CAKE_1,
+        duration_ms: 150,
+    },
+    Keyframe {
+        image: &assets::CAKE_2,
+        duration_ms: 100,
+    },
+    Keyframe {
+        image: &assets::CAKE_3,
+        duration_ms: 150,
+    },
+    Keyframe {
+        image: &assets::CAKE_4,
+        duration_ms: 100,
+    },
+    Keyframe {
+        image: &assets::CAKE_5,
+        duration_ms: 200,
+    },
+    Keyframe {
+        image: &assets::CAKE_6,
+        duration_ms: 100,
+    },
+];
+const FRAME_SPACING_MS: u16 = 50;
+
+pub struct Particle {
+    age: u8,
+    row: u8,
+    col: u8,
+}
+
+pub struct Cake<const N: usize> {
+    particles: Vec<Particle, N>,
+    flame: Animation<6>,
+    rng: EffectRng,
+    /// The chance of a new spark leaving the wick each frame
+    spawn: f32,
+    /// How many frames a particle lives for
+    life: u8,
+    /// The colours of a particle as it ages: its first frame out of the wick, then the rest
+    colours: [RGB8; 2],
+}
+
+const COLOURS: [RGB8; 2] = [RGB8::new(255, 130, 0), RGB8::new(255, 80, 0)];
+
+impl<const N: usize> Cake<N> {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            particles: Vec::new(),
+            flame: Animation::new(&FLAME, PlayMode::Loop, FRAME_SPACING_MS).unwrap(),
+            rng: EffectRng::new(seed),
+            spawn: 0.4,
+            life: 5,
+            colours: COLOURS,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+}
+
+impl<const N: usize> Tunable for Cake<N> {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "spawn",
+            kind: ParamKind::Float { min: 0.0, max: 1.0 },
+            default: ParamValue::Float(0.4),
+        },
+        ParamSpec {
+            name: "life",
+            kind: ParamKind::Int { min: 1, max: 12 },
+            default: ParamValue::Int(5),
+        },
+        ParamSpec {
+            name: "flame",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOURS[0]),
+        },
+        ParamSpec {
+            name: "ember",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOURS[1]),
+        },
+        // how the candle's flame plays
+        Animation::<6>::PARAMS[0],
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            0 => ParamValue::Float(self.spawn),
+            1 => ParamValue::Int(self.life as i32),
+            2..=3 => ParamValue::Colour(self.colours[index - 2]),
+            4 => return self.flame.param(0),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Float(spawn)) => self.spawn = spawn,
+            (1, ParamValue::Int(life)) => self.life = life as u8,
+            (2..=3, ParamValue::Colour(colour)) => self.colours[index - 2] = colour,
+            (4, value) => self.flame.set_param(0, value),
+            _ => (),
+        }
+    }
+}
+
+impl<const N: usize, const ROWS: usize, const COLS: usize> MatrixDisplayer<ROWS, COLS> for Cake<N> {
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        self.flame.update(buffer);
+        // the wick, which is off the buffer if it's too small to hold the cake
+        let (row, col) = (3, 6);
+        if row < ROWS
+            && col < COLS
+            && !self.particles.is_full()
+            && self.rng.gen_bool(self.spawn as f64)
+        {
+            let particle = Particle {
+                age: 0,
+                row: row as u8,
+                col: col as u8,
+            };
+            // there's room, having just checked
+            self.particles.push(particle).ok();
+        }
+        let (rng, life) = (&mut self.rng, self.life);
+        self.particles.retain_mut(|p| {
+            p.age += 1;
+            // sparks gather along the top and sides of the buffer rather than leaving it
+            p.row = p.row.saturating_sub(1);
+            match rng.gen_range(0..3) {
+                0 => p.col = p.col.saturating_sub(1),
+                1 => (),
+                2 if (p.col as usize) < COLS - 1 => p.col += 1,
+                2 => (),
+                _ => unreachable!(),
+            };
+            p.age <= life
+        });
+        self.particles.iter().for_each(|p| {
+            buffer[(p.row as usize, p.col as usize)] = self.colours[(p.age as usize - 1).min(1)];
+        });
+    }
+}
@@ -0,0 +1,356 @@
+//! Stacks other displays over the one the [`Switcher`] is running, so e.g. a text ticker can run
+//! over Metaballs or the cake over a rainbow. Each layer runs at its own frame spacing into its
+//! own frame, which is merged onto the ones below with a [`Blend`] mode and an opacity.
+//!
+//! A stack is written bottom to top as a list of `id[:blend[:opacity]]` layers above the running
+//! display, e.g. `7:screen,2:normal:128`, or `none` for no layers. The blend defaults to normal
+//! and the opacity, out of 255, to fully opaque.
+//!
+//! The [`zones`](super::zones) of a layout are drawn over the layers, each at its own pace too.
+
+use core::{fmt, mem, str::FromStr};
+
+use heapless::Vec;
+
+use super::{
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::{Displays, EFFECTS},
+    rgb8::RGB8,
+    rng::splitmix64,
+    transition::Switcher,
+    zones::{Layout, LayoutError, Zones},
+};
+
+/// The most layers that can be stacked over the running display
+pub const MAX_LAYERS: usize = 4;
+
+/// How a layer's pixels are merged with what is below them
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Blend {
+    /// The layer covers what is below, apart from where it is black. The effects have no alpha
+    /// channel, so black is taken as see-through.
+    Normal,
+    /// Add the channels together, so layers brighten each other
+    Add,
+    /// Multiply the channels, so the layer darkens what is below it
+    Multiply,
+    /// The inverse of multiplying the inverses, brightening without going straight to white
+    Screen,
+    /// The brighter of each channel
+    Max,
+}
+
+impl Blend {
+    const NAMES: [(&'static str, Self); 5] = [
+        ("normal", Self::Normal),
+        ("add", Self::Add),
+        ("multiply", Self::Multiply),
+        ("screen", Self::Screen),
+        ("max", Self::Max),
+    ];
+
+    fn name(&self) -> &'static str {
+        Self::NAMES.iter().find(|(_, b)| b == self).unwrap().0
+    }
+
+    /// Merge `above` onto `below`, with `opacity` out of 255 of the blended colour showing
+    pub fn apply(self, below: RGB8, above: RGB8, opacity: u8) -> RGB8 {
+        let blended = match self {
+            Self::Normal if above == RGB8::default() => below,
+            Self::Normal => above,
+            Self::Add => channels(below, above, u8::saturating_add),
+            Self::Multiply => channels(below, above, |a, b| (a as u16 * b as u16 / 255) as u8),
+            Self::Screen => channels(below, above, |a, b| {
+                255 - ((255 - a) as u16 * (255 - b) as u16 / 255) as u8
+            }),
+            Self::Max => channels(below, above, u8::max),
+        };
+        channels(below, blended, |a, b| {
+            ((a as u16 * (255 - opacity) as u16 + b as u16 * opacity as u16) / 255) as u8
+        })
+    }
+}
+
+fn channels(a: RGB8, b: RGB8, f: impl Fn(u8, u8) -> u8) -> RGB8 {
+    RGB8::with_white(f(a.r, b.r), f(a.g, b.g), f(a.b, b.b), f(a.w, b.w))
+}
+
+impl fmt::Display for Blend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Blend {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, blend) = Self::NAMES.iter().find(|(name, _)| *name == s).ok_or(())?;
+        Ok(*blend)
+    }
+}
+
+/// One layer of a [`Stack`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LayerSpec {
+    /// The display id, as used by [`Displays::with_seed`]
+    pub id: u8,
+    pub blend: Blend,
+    pub opacity: u8,
+}
+
+/// The layers to run over the running display, bottom first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack {
+    pub layers: Vec<LayerSpec, MAX_LAYERS>,
+}
+
+impl Stack {
+    pub const fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.layers.is_empty() {
+            return f.write_str("none");
+        }
+        let mut separator = "";
+        for layer in &self.layers {
+            write!(
+                f,
+                "{}{}:{}:{}",
+                separator, layer.id, layer.blend, layer.opacity
+            )?;
+            separator = ",";
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Stack {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stack = Self::new();
+        if s == "none" {
+            return Ok(stack);
+        }
+        for layer in s.split(',') {
+            let mut fields = layer.split(':');
+            let id: u8 = fields.next().ok_or(())?.parse().map_err(|_| ())?;
+            let blend = match fields.next() {
+                Some(blend) => blend.parse()?,
+                None => Blend::Normal,
+            };
+            let opacity = match fields.next() {
+                Some(opacity) => opacity.parse().map_err(|_| ())?,
+                None => 255,
+            };
+            if fields.next().is_some() || id as usize >= EFFECTS.len() {
+                return Err(());
+            }
+            let layer = LayerSpec { id, blend, opacity };
+            stack.layers.push(layer).map_err(|_| ())?;
+        }
+        Ok(stack)
+    }
+}
+
+/// How long since something was last drawn, starting out due straight away
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Clock {
+    since_ms: u32,
+}
+
+impl Clock {
+    pub(crate) const DUE: Self = Self { since_ms: u32::MAX };
+
+    /// Move the clock on, returning whether it is time to draw again
+    pub(crate) fn tick(&mut self, elapsed_ms: u32, spacing: u64) -> bool {
+        self.since_ms = self.since_ms.saturating_add(elapsed_ms);
+        if (self.since_ms as u64) < spacing {
+            return false;
+        }
+        // keep any overshoot so the pace stays even, but don't try to catch up whole frames
+        let overshoot = self.since_ms as u64 - spacing;
+        self.since_ms = if overshoot < spacing {
+            overshoot as u32
+        } else {
+            0
+        };
+        true
+    }
+
+    pub(crate) fn until_due(&self, spacing: u64) -> u64 {
+        spacing.saturating_sub(self.since_ms as u64)
+    }
+}
+
+/// A display running over the ones below, and its last frame
+pub struct Layer<const ROWS: usize, const COLS: usize> {
+    pub display: Displays,
+    pub blend: Blend,
+    pub opacity: u8,
+    frame: Frame<ROWS, COLS>,
+    clock: Clock,
+}
+
+/// Runs the layers of a [`Stack`] and the zones of a [`Layout`], and merges them over the
+/// switcher's display
+pub struct Compositor<const ROWS: usize, const COLS: usize> {
+    layers: Vec<Layer<ROWS, COLS>, MAX_LAYERS>,
+    zones: Zones,
+    /// The switcher's last frame, kept while there are layers or zones since they are drawn over
+    /// it. Without them the switcher draws straight onto the LEDs.
+    base: Option<Frame<ROWS, COLS>>,
+    base_clock: Clock,
+    /// The time since the switcher last drew under the layers or zones, for timing its
+    /// transitions
+    base_elapsed_ms: u32,
+}
+
+impl<const ROWS: usize, const COLS: usize> Compositor<ROWS, COLS> {
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            zones: Zones::new(),
+            base: None,
+            base_clock: Clock::DUE,
+            base_elapsed_ms: 0,
+        }
+    }
+
+    pub fn layers(&self) -> &[Layer<ROWS, COLS>] {
+        &self.layers
+    }
+
+    pub fn stack(&self) -> Stack {
+        let layers = self.layers.iter().map(|layer| LayerSpec {
+            id: layer.display.info().id as u8,
+            blend: layer.blend,
+            opacity: layer.opacity,
+        });
+        Stack {
+            layers: layers.collect(),
+        }
+    }
+
+    /// Run the layers of `stack`. A layer whose display is already running at that height keeps
+    /// running, and new ones are seeded from `seed`.
+    pub fn set_stack(&mut self, stack: &Stack, seed: u64) {
+        let mut old = mem::take(&mut self.layers).into_iter();
+        for (i, spec) in stack.layers.iter().enumerate() {
+            let layer = match old.next() {
+                Some(layer) if layer.display.info().id == spec.id as usize => Layer {
+                    blend: spec.blend,
+                    opacity: spec.opacity,
+                    ..layer
+                },
+                _ => Layer {
+                    display: Displays::with_seed(
+                        spec.id as usize,
+                        splitmix64(seed.wrapping_add(i as u64)),
+                    )
+                    .unwrap(),
+                    blend: spec.blend,
+                    opacity: spec.opacity,
+                    frame: Frame::new(),
+                    clock: Clock::DUE,
+                },
+            };
+            // the stack holds no more than the layers have room for
+            let _ = self.layers.push(layer);
+        }
+    }
+
+    pub fn zones(&self) -> &Zones {
+        &self.zones
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.zones.layout()
+    }
+
+    /// Run the zones of `layout`, keeping the ones whose display is already running at the same
+    /// size and seeding new ones from `seed`. A layout that doesn't fit the matrix is refused.
+    pub fn set_layout(&mut self, layout: &Layout, seed: u64) -> Result<(), LayoutError> {
+        if !layout.fits(ROWS, COLS) {
+            return Err(LayoutError::DoesNotFit);
+        }
+        self.zones.set_layout(layout, seed);
+        Ok(())
+    }
+
+    /// Whether the switcher has the whole matrix to itself
+    fn is_empty(&self) -> bool {
+        self.layers.is_empty() && self.zones.is_empty()
+    }
+
+    /// The time until the next frame, which with layers or zones is whenever one of them or the
+    /// switcher's display is next due
+    pub fn frame_spacing(&self, switcher: &Switcher<ROWS, COLS>) -> u64 {
+        if self.is_empty() {
+            return switcher.frame_spacing();
+        }
+        let base = self.base_clock.until_due(switcher.frame_spacing());
+        let zones = self.zones.until_due().unwrap_or(u64::MAX);
+        self.layers
+            .iter()
+            .map(|l| l.clock.until_due(l.display.frame_spacing()))
+            .fold(base.min(zones), u64::min)
+    }
+
+    /// Draw the next frame into the buffer, `elapsed_ms` after the last one. Each display only
+    /// moves on once its own frame spacing has passed.
+    pub fn update(
+        &mut self,
+        switcher: &mut Switcher<ROWS, COLS>,
+        buffer: &mut impl FrameBuffer<ROWS, COLS>,
+        elapsed_ms: u32,
+    ) {
+        if self.is_empty() {
+            if let Some(base) = self.base.take() {
+                // the layers and zones have just gone, so carry on from the switcher's own last frame
+                base.pixels().for_each(|(pos, p)| buffer[pos] = p);
+            }
+            switcher.update(buffer, elapsed_ms);
+            return;
+        }
+        let base = self.base.get_or_insert_with(|| {
+            // layers or zones have just been added, so carry on from what the switcher last drew
+            self.base_clock = Clock::DUE;
+            self.base_elapsed_ms = 0;
+            let mut frame = Frame::new();
+            buffer.pixels().for_each(|(pos, p)| frame[pos] = p);
+            frame
+        });
+        self.base_elapsed_ms = self.base_elapsed_ms.saturating_add(elapsed_ms);
+        if self.base_clock.tick(elapsed_ms, switcher.frame_spacing()) {
+            switcher.update(base, mem::take(&mut self.base_elapsed_ms));
+        }
+        base.pixels().for_each(|(pos, p)| buffer[pos] = p);
+        for layer in &mut self.layers {
+            if layer.clock.tick(elapsed_ms, layer.display.frame_spacing()) {
+                layer.display.update(&mut layer.frame);
+            }
+            for (pos, p) in layer.frame.pixels() {
+                buffer[pos] = layer.blend.apply(buffer[pos], p, layer.opacity);
+            }
+        }
+        self.zones.update(buffer, elapsed_ms);
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Default for Compositor<ROWS, COLS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,68 @@
+//! The last step before colours go out to the LEDs. Effects work in perceptual 0-255 values;
+//! this applies gamma so gradients look even on the linear LEDs, and scales everything by one
+//! global brightness.
+
+use super::{math as ff, rgb8::RGB8};
+
+#[derive(Debug, Clone)]
+pub struct ColourCorrection {
+    /// Gamma in tenths, so 22 is a gamma of 2.2
+    gamma: u8,
+    brightness: u8,
+    lut: [u8; 256],
+}
+
+impl ColourCorrection {
+    pub const DEFAULT_GAMMA: u8 = 22;
+    pub const DEFAULT_BRIGHTNESS: u8 = 32;
+
+    pub fn new(gamma: u8, brightness: u8) -> Self {
+        let mut correction = Self {
+            gamma,
+            brightness,
+            lut: [0; 256],
+        };
+        correction.fill_lut();
+        correction
+    }
+
+    pub fn gamma(&self) -> u8 {
+        self.gamma
+    }
+
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    pub fn set(&mut self, gamma: u8, brightness: u8) {
+        if (gamma, brightness) != (self.gamma, self.brightness) {
+            self.gamma = gamma;
+            self.brightness = brightness;
+            self.fill_lut();
+        }
+    }
+
+    fn fill_lut(&mut self) {
+        let gamma = ff::fdiv(ff::int_to_float(self.gamma as i32), 10.0);
+        let brightness = ff::int_to_float(self.brightness as i32);
+        for (i, out) in self.lut.iter_mut().enumerate() {
+            let level = ff::fpow(ff::fdiv(ff::int_to_float(i as i32), 255.0), gamma);
+            *out = ff::float_to_uint(ff::fadd(ff::fmul(level, brightness), 0.5)) as u8;
+        }
+    }
+
+    pub fn apply(&self, colour: RGB8) -> RGB8 {
+        RGB8::with_white(
+            self.lut[colour.r as usize],
+            self.lut[colour.g as usize],
+            self.lut[colour.b as usize],
+            self.lut[colour.w as usize],
+        )
+    }
+}
+
+impl Default for ColourCorrection {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_GAMMA, Self::DEFAULT_BRIGHTNESS)
+    }
+}
@@ -0,0 +1,358 @@
+//! Small monospaced bitmap fonts for the printable ASCII characters. The 5x7 font is the
+//! public domain X11 misc-fixed 5x7 and the 8x8 one is the public domain font8x8.
+
+/// The first character a font has a glyph for; the rest follow in order up to `~`
+const FIRST: char = ' ';
+const COUNT: usize = 95;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Font {
+    pub name: &'static str,
+    /// The size of each glyph in pixels
+    pub width: usize,
+    pub height: usize,
+    /// How far along the next character starts, including any gap after this one
+    pub advance: usize,
+    /// `height` rows per character, with bit 0 of each row being its leftmost pixel
+    glyphs: &'static [u8],
+}
+
+impl Font {
+    /// The rows of the character's glyph, or of `?` for characters the font doesn't have
+    pub fn glyph(&self, c: char) -> &'static [u8] {
+        let index = (c as usize)
+            .checked_sub(FIRST as usize)
+            .filter(|&i| i < COUNT)
+            .unwrap_or('?' as usize - FIRST as usize);
+        &self.glyphs[index * self.height..(index + 1) * self.height]
+    }
+
+    /// How many columns the text takes up written along one line
+    pub fn text_width(&self, text: &str) -> usize {
+        text.chars().count() * self.advance
+    }
+}
+
+/// The smallest, with lowercase letters squeezed into four rows
+pub const FONT_3X5: Font = Font {
+    name: "3x5",
+    width: 3,
+    height: 5,
+    advance: 4,
+    glyphs: &GLYPHS_3X5,
+};
+
+/// The glyphs are four pixels wide, with the gap built in
+pub const FONT_5X7: Font = Font {
+    name: "5x7",
+    width: 5,
+    height: 7,
+    advance: 5,
+    glyphs: &GLYPHS_5X7,
+};
+
+/// Most glyphs leave the right column and bottom row empty as a gap
+pub const FONT_8X8: Font = Font {
+    name: "8x8",
+    width: 8,
+    height: 8,
+    advance: 8,
+    glyphs: &GLYPHS_8X8,
+};
+
+/// Every font, smallest first
+pub const FONTS: [&Font; 3] = [&FONT_3X5, &FONT_5X7, &FONT_8X8];
+
+const GLYPHS_3X5: [u8; 475] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, // space
+    0x02, 0x02, 0x02, 0x00, 0x02, // !
+    0x05, 0x05, 0x00, 0x00, 0x00, // "
+    0x05, 0x07, 0x05, 0x07, 0x05, // #
+    0x06, 0x03, 0x02, 0x06, 0x03, // $
+    0x05, 0x04, 0x02, 0x01, 0x05, // %
+    0x02, 0x05, 0x02, 0x05, 0x06, // &
+    0x02, 0x02, 0x00, 0x00, 0x00, // '
+    0x04, 0x02, 0x02, 0x02, 0x04, // (
+    0x01, 0x02, 0x02, 0x02, 0x01, // )
+    0x00, 0x05, 0x02, 0x05, 0x00, // *
+    0x00, 0x02, 0x07, 0x02, 0x00, // +
+    0x00, 0x00, 0x00, 0x02, 0x01, // ,
+    0x00, 0x00, 0x07, 0x00, 0x00, // -
+    0x00, 0x00, 0x00, 0x00, 0x02, // .
+    0x04, 0x04, 0x02, 0x01, 0x01, // /
+    0x07, 0x05, 0x05, 0x05, 0x07, // 0
+    0x02, 0x03, 0x02, 0x02, 0x07, // 1
+    0x03, 0x04, 0x02, 0x01, 0x07, // 2
+    0x03, 0x04, 0x02, 0x04, 0x03, // 3
+    0x05, 0x05, 0x07, 0x04, 0x04, // 4
+    0x07, 0x01, 0x03, 0x04, 0x03, // 5
+    0x06, 0x01, 0x07, 0x05, 0x07, // 6
+    0x07, 0x04, 0x02, 0x02, 0x02, // 7
+    0x07, 0x05, 0x07, 0x05, 0x07, // 8
+    0x07, 0x05, 0x07, 0x04, 0x03, // 9
+    0x00, 0x02, 0x00, 0x02, 0x00, // :
+    0x00, 0x02, 0x00, 0x02, 0x01, // ;
+    0x04, 0x02, 0x01, 0x02, 0x04, // <
+    0x00, 0x07, 0x00, 0x07, 0x00, // =
+    0x01, 0x02, 0x04, 0x02, 0x01, // >
+    0x03, 0x04, 0x02, 0x00, 0x02, // ?
+    0x02, 0x05, 0x05, 0x01, 0x06, // @
+    0x02, 0x05, 0x07, 0x05, 0x05, // A
+    0x03, 0x05, 0x03, 0x05, 0x03, // B
+    0x06, 0x01, 0x01, 0x01, 0x06, // C
+    0x03, 0x05, 0x05, 0x05, 0x03, // D
+    0x07, 0x01, 0x03, 0x01, 0x07, // E
+    0x07, 0x01, 0x03, 0x01, 0x01, // F
+    0x06, 0x01, 0x05, 0x05, 0x06, // G
+    0x05, 0x05, 0x07, 0x05, 0x05, // H
+    0x07, 0x02, 0x02, 0x02, 0x07, // I
+    0x04, 0x04, 0x04, 0x05, 0x02, // J
+    0x05, 0x05, 0x03, 0x05, 0x05, // K
+    0x01, 0x01, 0x01, 0x01, 0x07, // L
+    0x05, 0x07, 0x07, 0x05, 0x05, // M
+    0x03, 0x05, 0x05, 0x05, 0x05, // N
+    0x02, 0x05, 0x05, 0x05, 0x02, // O
+    0x03, 0x05, 0x03, 0x01, 0x01, // P
+    0x02, 0x05, 0x05, 0x03, 0x06, // Q
+    0x03, 0x05, 0x03, 0x05, 0x05, // R
+    0x06, 0x01, 0x02, 0x04, 0x03, // S
+    0x07, 0x02, 0x02, 0x02, 0x02, // T
+    0x05, 0x05, 0x05, 0x05, 0x06, // U
+    0x05, 0x05, 0x05, 0x02, 0x02, // V
+    0x05, 0x05, 0x07, 0x07, 0x05, // W
+    0x05, 0x05, 0x02, 0x05, 0x05, // X
+    0x05, 0x05, 0x02, 0x02, 0x02, // Y
+    0x07, 0x04, 0x02, 0x01, 0x07, // Z
+    0x03, 0x01, 0x01, 0x01, 0x03, // [
+    0x01, 0x01, 0x02, 0x04, 0x04, // \
+    0x06, 0x04, 0x04, 0x04, 0x06, // ]
+    0x02, 0x05, 0x00, 0x00, 0x00, // ^
+    0x00, 0x00, 0x00, 0x00, 0x07, // _
+    0x01, 0x02, 0x00, 0x00, 0x00, // `
+    0x00, 0x06, 0x05, 0x05, 0x06, // a
+    0x01, 0x03, 0x05, 0x05, 0x03, // b
+    0x00, 0x06, 0x01, 0x01, 0x06, // c
+    0x04, 0x06, 0x05, 0x05, 0x06, // d
+    0x00, 0x06, 0x07, 0x01, 0x06, // e
+    0x04, 0x02, 0x07, 0x02, 0x02, // f
+    0x00, 0x06, 0x05, 0x06, 0x03, // g
+    0x01, 0x03, 0x05, 0x05, 0x05, // h
+    0x02, 0x00, 0x02, 0x02, 0x02, // i
+    0x04, 0x00, 0x04, 0x05, 0x02, // j
+    0x01, 0x05, 0x03, 0x03, 0x05, // k
+    0x03, 0x02, 0x02, 0x02, 0x07, // l
+    0x00, 0x07, 0x07, 0x07, 0x05, // m
+    0x00, 0x03, 0x05, 0x05, 0x05, // n
+    0x00, 0x02, 0x05, 0x05, 0x02, // o
+    0x00, 0x03, 0x05, 0x03, 0x01, // p
+    0x00, 0x06, 0x05, 0x06, 0x04, // q
+    0x00, 0x06, 0x01, 0x01, 0x01, // r
+    0x00, 0x06, 0x03, 0x06, 0x03, // s
+    0x02, 0x07, 0x02, 0x02, 0x04, // t
+    0x00, 0x05, 0x05, 0x05, 0x06, // u
+    0x00, 0x05, 0x05, 0x02, 0x02, // v
+    0x00, 0x05, 0x07, 0x07, 0x07, // w
+    0x00, 0x05, 0x02, 0x02, 0x05, // x
+    0x00, 0x05, 0x05, 0x02, 0x01, // y
+    0x00, 0x07, 0x06, 0x03, 0x07, // z
+    0x06, 0x02, 0x03, 0x02, 0x06, // {
+    0x02, 0x02, 0x02, 0x02, 0x02, // |
+    0x03, 0x02, 0x06, 0x02, 0x03, // }
+    0x00, 0x06, 0x03, 0x00, 0x00, // ~
+];
+
+const GLYPHS_5X7: [u8; 665] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // space
+    0x04, 0x04, 0x04, 0x04, 0x00, 0x04, 0x00, // !
+    0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00, // "
+    0x00, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x00, // #
+    0x00, 0x0e, 0x05, 0x0e, 0x14, 0x0e, 0x00, // $
+    0x01, 0x09, 0x04, 0x02, 0x09, 0x08, 0x00, // %
+    0x00, 0x02, 0x05, 0x02, 0x05, 0x0a, 0x00, // &
+    0x04, 0x04, 0x04, 0x00, 0x00, 0x00, 0x00, // '
+    0x04, 0x02, 0x02, 0x02, 0x02, 0x04, 0x00, // (
+    0x02, 0x04, 0x04, 0x04, 0x04, 0x02, 0x00, // )
+    0x00, 0x0a, 0x04, 0x0e, 0x04, 0x0a, 0x00, // *
+    0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00, // +
+    0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x02, // ,
+    0x00, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, // -
+    0x00, 0x00, 0x00, 0x00, 0x06, 0x06, 0x00, // .
+    0x00, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00, // /
+    0x04, 0x0a, 0x0a, 0x0a, 0x0a, 0x04, 0x00, // 0
+    0x04, 0x06, 0x04, 0x04, 0x04, 0x0e, 0x00, // 1
+    0x06, 0x09, 0x08, 0x04, 0x02, 0x0f, 0x00, // 2
+    0x0f, 0x08, 0x06, 0x08, 0x09, 0x06, 0x00, // 3
+    0x04, 0x06, 0x05, 0x0f, 0x04, 0x04, 0x00, // 4
+    0x0f, 0x01, 0x07, 0x08, 0x09, 0x06, 0x00, // 5
+    0x06, 0x01, 0x07, 0x09, 0x09, 0x06, 0x00, // 6
+    0x0f, 0x08, 0x04, 0x04, 0x02, 0x02, 0x00, // 7
+    0x06, 0x09, 0x06, 0x09, 0x09, 0x06, 0x00, // 8
+    0x06, 0x09, 0x09, 0x0e, 0x08, 0x06, 0x00, // 9
+    0x00, 0x06, 0x06, 0x00, 0x06, 0x06, 0x00, // :
+    0x00, 0x06, 0x06, 0x00, 0x06, 0x02, 0x01, // ;
+    0x00, 0x08, 0x04, 0x02, 0x04, 0x08, 0x00, // <
+    0x00, 0x00, 0x0f, 0x00, 0x0f, 0x00, 0x00, // =
+    0x00, 0x02, 0x04, 0x08, 0x04, 0x02, 0x00, // >
+    0x04, 0x0a, 0x08, 0x04, 0x00, 0x04, 0x00, // ?
+    0x06, 0x09, 0x0d, 0x0d, 0x01, 0x06, 0x00, // @
+    0x06, 0x09, 0x09, 0x0f, 0x09, 0x09, 0x00, // A
+    0x07, 0x09, 0x07, 0x09, 0x09, 0x07, 0x00, // B
+    0x06, 0x09, 0x01, 0x01, 0x09, 0x06, 0x00, // C
+    0x07, 0x09, 0x09, 0x09, 0x09, 0x07, 0x00, // D
+    0x0f, 0x01, 0x07, 0x01, 0x01, 0x0f, 0x00, // E
+    0x0f, 0x01, 0x07, 0x01, 0x01, 0x01, 0x00, // F
+    0x06, 0x09, 0x01, 0x0d, 0x09, 0x0e, 0x00, // G
+    0x09, 0x09, 0x0f, 0x09, 0x09, 0x09, 0x00, // H
+    0x0e, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00, // I
+    0x08, 0x08, 0x08, 0x08, 0x09, 0x06, 0x00, // J
+    0x09, 0x05, 0x03, 0x03, 0x05, 0x09, 0x00, // K
+    0x01, 0x01, 0x01, 0x01, 0x01, 0x0f, 0x00, // L
+    0x09, 0x0f, 0x0f, 0x09, 0x09, 0x09, 0x00, // M
+    0x09, 0x0b, 0x0b, 0x0d, 0x0d, 0x09, 0x00, // N
+    0x06, 0x09, 0x09, 0x09, 0x09, 0x06, 0x00, // O
+    0x07, 0x09, 0x09, 0x07, 0x01, 0x01, 0x00, // P
+    0x06, 0x09, 0x09, 0x09, 0x0b, 0x06, 0x08, // Q
+    0x07, 0x09, 0x09, 0x07, 0x05, 0x09, 0x00, // R
+    0x06, 0x09, 0x02, 0x04, 0x09, 0x06, 0x00, // S
+    0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00, // T
+    0x09, 0x09, 0x09, 0x09, 0x09, 0x06, 0x00, // U
+    0x09, 0x09, 0x09, 0x09, 0x06, 0x06, 0x00, // V
+    0x09, 0x09, 0x09, 0x0f, 0x0f, 0x09, 0x00, // W
+    0x09, 0x09, 0x06, 0x06, 0x09, 0x09, 0x00, // X
+    0x0a, 0x0a, 0x0a, 0x04, 0x04, 0x04, 0x00, // Y
+    0x0f, 0x08, 0x04, 0x02, 0x01, 0x0f, 0x00, // Z
+    0x0e, 0x02, 0x02, 0x02, 0x02, 0x0e, 0x00, // [
+    0x00, 0x01, 0x02, 0x04, 0x08, 0x00, 0x00, // \
+    0x0e, 0x08, 0x08, 0x08, 0x08, 0x0e, 0x00, // ]
+    0x04, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, // ^
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x00, // _
+    0x02, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, // `
+    0x00, 0x00, 0x0e, 0x09, 0x0d, 0x0a, 0x00, // a
+    0x01, 0x01, 0x07, 0x09, 0x09, 0x07, 0x00, // b
+    0x00, 0x00, 0x06, 0x01, 0x01, 0x06, 0x00, // c
+    0x08, 0x08, 0x0e, 0x09, 0x09, 0x0e, 0x00, // d
+    0x00, 0x00, 0x06, 0x0d, 0x03, 0x06, 0x00, // e
+    0x04, 0x0a, 0x02, 0x07, 0x02, 0x02, 0x00, // f
+    0x00, 0x00, 0x0e, 0x09, 0x06, 0x01, 0x0e, // g
+    0x01, 0x01, 0x07, 0x09, 0x09, 0x09, 0x00, // h
+    0x04, 0x00, 0x06, 0x04, 0x04, 0x0e, 0x00, // i
+    0x08, 0x00, 0x08, 0x08, 0x08, 0x0a, 0x04, // j
+    0x01, 0x01, 0x05, 0x03, 0x05, 0x09, 0x00, // k
+    0x06, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00, // l
+    0x00, 0x00, 0x05, 0x0f, 0x09, 0x09, 0x00, // m
+    0x00, 0x00, 0x07, 0x09, 0x09, 0x09, 0x00, // n
+    0x00, 0x00, 0x06, 0x09, 0x09, 0x06, 0x00, // o
+    0x00, 0x00, 0x07, 0x09, 0x09, 0x07, 0x01, // p
+    0x00, 0x00, 0x0e, 0x09, 0x09, 0x0e, 0x08, // q
+    0x00, 0x00, 0x07, 0x09, 0x01, 0x01, 0x00, // r
+    0x00, 0x00, 0x0e, 0x03, 0x0c, 0x07, 0x00, // s
+    0x02, 0x02, 0x07, 0x02, 0x02, 0x0c, 0x00, // t
+    0x00, 0x00, 0x09, 0x09, 0x09, 0x0e, 0x00, // u
+    0x00, 0x00, 0x0a, 0x0a, 0x0a, 0x04, 0x00, // v
+    0x00, 0x00, 0x09, 0x09, 0x0f, 0x0f, 0x00, // w
+    0x00, 0x00, 0x09, 0x06, 0x06, 0x09, 0x00, // x
+    0x00, 0x00, 0x09, 0x09, 0x0a, 0x04, 0x02, // y
+    0x00, 0x00, 0x0f, 0x04, 0x02, 0x0f, 0x00, // z
+    0x08, 0x04, 0x06, 0x04, 0x04, 0x08, 0x00, // {
+    0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00, // |
+    0x02, 0x04, 0x0c, 0x04, 0x04, 0x02, 0x00, // }
+    0x0a, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, // ~
+];
+
+const GLYPHS_8X8: [u8; 760] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // space
+    0x18, 0x3c, 0x3c, 0x18, 0x18, 0x00, 0x18, 0x00, // !
+    0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // "
+    0x36, 0x36, 0x7f, 0x36, 0x7f, 0x36, 0x36, 0x00, // #
+    0x0c, 0x3e, 0x03, 0x1e, 0x30, 0x1f, 0x0c, 0x00, // $
+    0x00, 0x63, 0x33, 0x18, 0x0c, 0x66, 0x63, 0x00, // %
+    0x1c, 0x36, 0x1c, 0x6e, 0x3b, 0x33, 0x6e, 0x00, // &
+    0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, // '
+    0x18, 0x0c, 0x06, 0x06, 0x06, 0x0c, 0x18, 0x00, // (
+    0x06, 0x0c, 0x18, 0x18, 0x18, 0x0c, 0x06, 0x00, // )
+    0x00, 0x66, 0x3c, 0xff, 0x3c, 0x66, 0x00, 0x00, // *
+    0x00, 0x0c, 0x0c, 0x3f, 0x0c, 0x0c, 0x00, 0x00, // +
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x06, // ,
+    0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0x00, // -
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00, // .
+    0x60, 0x30, 0x18, 0x0c, 0x06, 0x03, 0x01, 0x00, // /
+    0x3e, 0x63, 0x73, 0x7b, 0x6f, 0x67, 0x3e, 0x00, // 0
+    0x0c, 0x0e, 0x0c, 0x0c, 0x0c, 0x0c, 0x3f, 0x00, // 1
+    0x1e, 0x33, 0x30, 0x1c, 0x06, 0x33, 0x3f, 0x00, // 2
+    0x1e, 0x33, 0x30, 0x1c, 0x30, 0x33, 0x1e, 0x00, // 3
+    0x38, 0x3c, 0x36, 0x33, 0x7f, 0x30, 0x78, 0x00, // 4
+    0x3f, 0x03, 0x1f, 0x30, 0x30, 0x33, 0x1e, 0x00, // 5
+    0x1c, 0x06, 0x03, 0x1f, 0x33, 0x33, 0x1e, 0x00, // 6
+    0x3f, 0x33, 0x30, 0x18, 0x0c, 0x0c, 0x0c, 0x00, // 7
+    0x1e, 0x33, 0x33, 0x1e, 0x33, 0x33, 0x1e, 0x00, // 8
+    0x1e, 0x33, 0x33, 0x3e, 0x30, 0x18, 0x0e, 0x00, // 9
+    0x00, 0x0c, 0x0c, 0x00, 0x00, 0x0c, 0x0c, 0x00, // :
+    0x00, 0x0c, 0x0c, 0x00, 0x00, 0x0c, 0x0c, 0x06, // ;
+    0x18, 0x0c, 0x06, 0x03, 0x06, 0x0c, 0x18, 0x00, // <
+    0x00, 0x00, 0x3f, 0x00, 0x00, 0x3f, 0x00, 0x00, // =
+    0x06, 0x0c, 0x18, 0x30, 0x18, 0x0c, 0x06, 0x00, // >
+    0x1e, 0x33, 0x30, 0x18, 0x0c, 0x00, 0x0c, 0x00, // ?
+    0x3e, 0x63, 0x7b, 0x7b, 0x7b, 0x03, 0x1e, 0x00, // @
+    0x0c, 0x1e, 0x33, 0x33, 0x3f, 0x33, 0x33, 0x00, // A
+    0x3f, 0x66, 0x66, 0x3e, 0x66, 0x66, 0x3f, 0x00, // B
+    0x3c, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3c, 0x00, // C
+    0x1f, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1f, 0x00, // D
+    0x7f, 0x46, 0x16, 0x1e, 0x16, 0x46, 0x7f, 0x00, // E
+    0x7f, 0x46, 0x16, 0x1e, 0x16, 0x06, 0x0f, 0x00, // F
+    0x3c, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7c, 0x00, // G
+    0x33, 0x33, 0x33, 0x3f, 0x33, 0x33, 0x33, 0x00, // H
+    0x1e, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x1e, 0x00, // I
+    0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1e, 0x00, // J
+    0x67, 0x66, 0x36, 0x1e, 0x36, 0x66, 0x67, 0x00, // K
+    0x0f, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7f, 0x00, // L
+    0x63, 0x77, 0x7f, 0x7f, 0x6b, 0x63, 0x63, 0x00, // M
+    0x63, 0x67, 0x6f, 0x7b, 0x73, 0x63, 0x63, 0x00, // N
+    0x1c, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1c, 0x00, // O
+    0x3f, 0x66, 0x66, 0x3e, 0x06, 0x06, 0x0f, 0x00, // P
+    0x1e, 0x33, 0x33, 0x33, 0x3b, 0x1e, 0x38, 0x00, // Q
+    0x3f, 0x66, 0x66, 0x3e, 0x36, 0x66, 0x67, 0x00, // R
+    0x1e, 0x33, 0x07, 0x0e, 0x38, 0x33, 0x1e, 0x00, // S
+    0x3f, 0x2d, 0x0c, 0x0c, 0x0c, 0x0c, 0x1e, 0x00, // T
+    0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3f, 0x00, // U
+    0x33, 0x33, 0x33, 0x33, 0x33, 0x1e, 0x0c, 0x00, // V
+    0x63, 0x63, 0x63, 0x6b, 0x7f, 0x77, 0x63, 0x00, // W
+    0x63, 0x63, 0x36, 0x1c, 0x1c, 0x36, 0x63, 0x00, // X
+    0x33, 0x33, 0x33, 0x1e, 0x0c, 0x0c, 0x1e, 0x00, // Y
+    0x7f, 0x63, 0x31, 0x18, 0x4c, 0x66, 0x7f, 0x00, // Z
+    0x1e, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1e, 0x00, // [
+    0x03, 0x06, 0x0c, 0x18, 0x30, 0x60, 0x40, 0x00, // \
+    0x1e, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1e, 0x00, // ]
+    0x08, 0x1c, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00, // ^
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, // _
+    0x0c, 0x0c, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, // `
+    0x00, 0x00, 0x1e, 0x30, 0x3e, 0x33, 0x6e, 0x00, // a
+    0x07, 0x06, 0x06, 0x3e, 0x66, 0x66, 0x3b, 0x00, // b
+    0x00, 0x00, 0x1e, 0x33, 0x03, 0x33, 0x1e, 0x00, // c
+    0x38, 0x30, 0x30, 0x3e, 0x33, 0x33, 0x6e, 0x00, // d
+    0x00, 0x00, 0x1e, 0x33, 0x3f, 0x03, 0x1e, 0x00, // e
+    0x1c, 0x36, 0x06, 0x0f, 0x06, 0x06, 0x0f, 0x00, // f
+    0x00, 0x00, 0x6e, 0x33, 0x33, 0x3e, 0x30, 0x1f, // g
+    0x07, 0x06, 0x36, 0x6e, 0x66, 0x66, 0x67, 0x00, // h
+    0x0c, 0x00, 0x0e, 0x0c, 0x0c, 0x0c, 0x1e, 0x00, // i
+    0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1e, // j
+    0x07, 0x06, 0x66, 0x36, 0x1e, 0x36, 0x67, 0x00, // k
+    0x0e, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x1e, 0x00, // l
+    0x00, 0x00, 0x33, 0x7f, 0x7f, 0x6b, 0x63, 0x00, // m
+    0x00, 0x00, 0x1f, 0x33, 0x33, 0x33, 0x33, 0x00, // n
+    0x00, 0x00, 0x1e, 0x33, 0x33, 0x33, 0x1e, 0x00, // o
+    0x00, 0x00, 0x3b, 0x66, 0x66, 0x3e, 0x06, 0x0f, // p
+    0x00, 0x00, 0x6e, 0x33, 0x33, 0x3e, 0x30, 0x78, // q
+    0x00, 0x00, 0x3b, 0x6e, 0x66, 0x06, 0x0f, 0x00, // r
+    0x00, 0x00, 0x3e, 0x03, 0x1e, 0x30, 0x1f, 0x00, // s
+    0x08, 0x0c, 0x3e, 0x0c, 0x0c, 0x2c, 0x18, 0x00, // t
+    0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6e, 0x00, // u
+    0x00, 0x00, 0x33, 0x33, 0x33, 0x1e, 0x0c, 0x00, // v
+    0x00, 0x00, 0x63, 0x6b, 0x7f, 0x7f, 0x36, 0x00, // w
+    0x00, 0x00, 0x63, 0x36, 0x1c, 0x36, 0x63, 0x00, // x
+    0x00, 0x00, 0x33, 0x33, 0x33, 0x3e, 0x30, 0x1f, // y
+    0x00, 0x00, 0x3f, 0x19, 0x0c, 0x26, 0x3f, 0x00, // z
+    0x38, 0x0c, 0x0c, 0x07, 0x0c, 0x0c, 0x38, 0x00, // {
+    0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00, // |
+    0x07, 0x0c, 0x0c, 0x38, 0x0c, 0x0c, 0x07, 0x00, // }
+    0x6e, 0x3b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // ~
+];
@@ -0,0 +1,58 @@
+use core::ops::{Index, IndexMut};
+
+use super::rgb8::RGB8;
+
+/// A hardware-neutral grid of pixels, addressed by (row, col) with (0, 0) in the top left.
+/// Effects draw into this rather than into a particular LED driver.
+pub trait FrameBuffer<const ROWS: usize, const COLS: usize>:
+    Index<(usize, usize), Output = RGB8> + IndexMut<(usize, usize)>
+{
+    fn fill(&mut self, colour: RGB8) {
+        for row in 0..ROWS {
+            for col in 0..COLS {
+                self[(row, col)] = colour;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.fill(RGB8::default());
+    }
+
+    /// Iterate over every pixel in row-major order, along with its (row, col) position
+    fn pixels(&self) -> impl Iterator<Item = ((usize, usize), RGB8)> {
+        (0..ROWS).flat_map(move |r| (0..COLS).map(move |c| ((r, c), self[(r, c)])))
+    }
+}
+
+/// An off-screen frame buffer stored in row-major order
+#[derive(Debug, Copy, Clone)]
+pub struct Frame<const ROWS: usize, const COLS: usize>(pub [[RGB8; COLS]; ROWS]);
+
+impl<const ROWS: usize, const COLS: usize> Frame<ROWS, COLS> {
+    pub fn new() -> Self {
+        Self([[RGB8::default(); COLS]; ROWS])
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Default for Frame<ROWS, COLS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Index<(usize, usize)> for Frame<ROWS, COLS> {
+    type Output = RGB8;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.0[row][col]
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> IndexMut<(usize, usize)> for Frame<ROWS, COLS> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.0[row][col]
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> FrameBuffer<ROWS, COLS> for Frame<ROWS, COLS> {}
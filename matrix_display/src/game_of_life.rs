@@ -0,0 +1,267 @@
+//! Conway's Game of Life on a board whose edges wrap round, so it is really a torus. Cells are
+//! coloured by how many generations they have been alive.
+//!
+//! A board that dies out or settles into a cycle is reseeded. Generations are hashed, and a hash
+//! matching one of the last [`HISTORY`] means the board has come back to a state it was in
+//! before, which covers still lifes, blinkers and a lone glider travelling round the torus.
+
+use rand::Rng;
+
+use super::{
+    frame_buffer::FrameBuffer,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamValue, Tunable},
+    rgb8::RGB8,
+    rng::EffectRng,
+    wheel::wheel,
+};
+
+/// How many past generations are checked for a repeat. A glider takes 4 generations to move one
+/// cell diagonally, so this catches one circling a 16x16 board.
+pub const HISTORY: usize = 64;
+
+/// How a board is filled when it starts or is reseeded
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Seeding {
+    /// Each cell is alive with the chance given by the density
+    Random,
+    Glider,
+    RPentomino,
+    Acorn,
+    /// A lightweight spaceship
+    Spaceship,
+}
+
+const SEEDINGS: &[&str] = &["random", "glider", "r-pentomino", "acorn", "spaceship"];
+
+impl Seeding {
+    /// The live cells of the pattern, as (row, col) offsets from its top left
+    fn pattern(self) -> &'static [(usize, usize)] {
+        match self {
+            Self::Random => &[],
+            Self::Glider => &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)],
+            Self::RPentomino => &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)],
+            Self::Acorn => &[(0, 1), (1, 3), (2, 0), (2, 1), (2, 4), (2, 5), (2, 6)],
+            Self::Spaceship => &[
+                (0, 1),
+                (0, 4),
+                (1, 0),
+                (2, 0),
+                (2, 4),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+            ],
+        }
+    }
+
+    fn from_index(i: u8) -> Option<Self> {
+        [
+            Self::Random,
+            Self::Glider,
+            Self::RPentomino,
+            Self::Acorn,
+            Self::Spaceship,
+        ]
+        .get(i as usize)
+        .copied()
+    }
+}
+
+pub struct GameOfLife<const ROWS: usize, const COLS: usize> {
+    cells: [[bool; COLS]; ROWS],
+    /// How many generations each live cell has survived
+    ages: [[u8; COLS]; ROWS],
+    /// Hashes of recent generations, oldest overwritten first
+    history: [u64; HISTORY],
+    generation: usize,
+    seeding: Seeding,
+    density: f32,
+    rng: EffectRng,
+}
+
+impl<const ROWS: usize, const COLS: usize> GameOfLife<ROWS, COLS> {
+    pub fn new(seed: u64) -> Self {
+        Self::with_seeding(seed, Seeding::Random)
+    }
+
+    pub fn with_seeding(seed: u64, seeding: Seeding) -> Self {
+        let mut life = Self::from_cells([[false; COLS]; ROWS], seed);
+        life.seeding = seeding;
+        life.reseed();
+        life
+    }
+
+    /// Start from the given board. It is still reseeded, randomly, once it dies or cycles.
+    pub fn from_cells(cells: [[bool; COLS]; ROWS], seed: u64) -> Self {
+        let mut life = Self {
+            cells,
+            ages: [[0; COLS]; ROWS],
+            history: [0; HISTORY],
+            generation: 0,
+            seeding: Seeding::Random,
+            density: 0.35,
+            rng: EffectRng::new(seed),
+        };
+        life.forget();
+        life
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn cells(&self) -> &[[bool; COLS]; ROWS] {
+        &self.cells
+    }
+
+    /// Fill the board afresh, placing patterns at a random spot
+    pub fn reseed(&mut self) {
+        self.cells = [[false; COLS]; ROWS];
+        match self.seeding {
+            Seeding::Random => {
+                let density = self.density as f64;
+                for row in self.cells.iter_mut() {
+                    for cell in row.iter_mut() {
+                        *cell = self.rng.gen_bool(density);
+                    }
+                }
+            }
+            seeding => {
+                let (top, left) = (self.rng.gen_range(0..ROWS), self.rng.gen_range(0..COLS));
+                for (r, c) in seeding.pattern() {
+                    self.cells[(top + r) % ROWS][(left + c) % COLS] = true;
+                }
+            }
+        }
+        self.forget();
+    }
+
+    /// Clear the ages and history, for a board that has just been filled
+    fn forget(&mut self) {
+        self.ages = [[0; COLS]; ROWS];
+        self.history = [0; HISTORY];
+        self.generation = 0;
+        let hash = self.hash();
+        self.remember(hash);
+    }
+
+    fn live_neighbours(&self, row: usize, col: usize) -> usize {
+        let mut count = 0;
+        for dr in [ROWS - 1, 0, 1] {
+            for dc in [COLS - 1, 0, 1] {
+                if (dr, dc) != (0, 0) && self.cells[(row + dr) % ROWS][(col + dc) % COLS] {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Advance one generation, reseeding if the board has died or repeated itself
+    pub fn step(&mut self) {
+        let mut next = [[false; COLS]; ROWS];
+        for (row, next_row) in next.iter_mut().enumerate() {
+            for (col, cell) in next_row.iter_mut().enumerate() {
+                *cell = matches!(
+                    (self.cells[row][col], self.live_neighbours(row, col)),
+                    (true, 2) | (_, 3)
+                );
+                let age = &mut self.ages[row][col];
+                *age = if *cell && self.cells[row][col] {
+                    age.saturating_add(1)
+                } else {
+                    0
+                };
+            }
+        }
+        self.cells = next;
+        let hash = self.hash();
+        if self.cells.iter().flatten().all(|c| !c) || self.history.contains(&hash) {
+            self.reseed();
+        } else {
+            self.remember(hash);
+        }
+    }
+
+    fn remember(&mut self, hash: u64) {
+        self.history[self.generation % HISTORY] = hash;
+        self.generation += 1;
+    }
+
+    /// FNV-1a over the cells, one bit each
+    fn hash(&self) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        for chunk in self.cells.as_flattened().chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |b, (i, &c)| b | (c as u8) << i);
+            hash = (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3);
+        }
+        hash
+    }
+
+    /// Newly born cells are green, shifting towards blue and purple as they age
+    fn colour(age: u8) -> RGB8 {
+        wheel(64 + age.min(40) * 3)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Tunable for GameOfLife<ROWS, COLS> {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "start",
+            kind: ParamKind::Enum(SEEDINGS),
+            default: ParamValue::Enum(0),
+        },
+        ParamSpec {
+            name: "density",
+            kind: ParamKind::Float {
+                min: 0.05,
+                max: 0.9,
+            },
+            default: ParamValue::Float(0.35),
+        },
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            0 => ParamValue::Enum(self.seeding as u8),
+            1 => ParamValue::Float(self.density),
+            _ => return None,
+        })
+    }
+
+    /// Either parameter starts a new board, so the change shows straight away
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Enum(i)) => match Seeding::from_index(i) {
+                Some(seeding) => self.seeding = seeding,
+                None => return,
+            },
+            (1, ParamValue::Float(density)) => self.density = density,
+            _ => return,
+        }
+        self.reseed();
+    }
+}
+
+/// Draws whatever part of the board overlaps the buffer
+impl<const R: usize, const C: usize, const ROWS: usize, const COLS: usize>
+    MatrixDisplayer<ROWS, COLS> for GameOfLife<R, C>
+{
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        for row in 0..ROWS.min(R) {
+            for col in 0..COLS.min(C) {
+                buffer[(row, col)] = if self.cells[row][col] {
+                    Self::colour(self.ages[row][col])
+                } else {
+                    RGB8::default()
+                };
+            }
+        }
+        self.step();
+    }
+}
@@ -0,0 +1,89 @@
+//! Lets the embedded-graphics ecosystem draw onto frame buffers. `x` runs along a row and `y` down
+//! the columns, so a point maps to `(row, col) = (y, x)`; anything outside the buffer is clipped.
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::Rgb888,
+    Pixel,
+};
+
+use super::{
+    frame_buffer::{Frame, FrameBuffer},
+    rgb8::RGB8,
+};
+
+/// Draw pixels into a frame buffer, skipping any that fall outside it
+pub fn draw_pixels<const ROWS: usize, const COLS: usize>(
+    buffer: &mut impl FrameBuffer<ROWS, COLS>,
+    pixels: impl IntoIterator<Item = Pixel<Rgb888>>,
+) {
+    for Pixel(point, colour) in pixels {
+        if let (Ok(row), Ok(col)) = (usize::try_from(point.y), usize::try_from(point.x)) {
+            if row < ROWS && col < COLS {
+                buffer[(row, col)] = colour.into();
+            }
+        }
+    }
+}
+
+pub fn size<const ROWS: usize, const COLS: usize>() -> Size {
+    Size::new(COLS as u32, ROWS as u32)
+}
+
+/// Borrows any frame buffer as an embedded-graphics draw target
+pub struct Canvas<'a, B, const ROWS: usize, const COLS: usize>(pub &'a mut B);
+
+impl<'a, B: FrameBuffer<ROWS, COLS>, const ROWS: usize, const COLS: usize> OriginDimensions
+    for Canvas<'a, B, ROWS, COLS>
+{
+    fn size(&self) -> Size {
+        size::<ROWS, COLS>()
+    }
+}
+
+impl<'a, B: FrameBuffer<ROWS, COLS>, const ROWS: usize, const COLS: usize> DrawTarget
+    for Canvas<'a, B, ROWS, COLS>
+{
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        draw_pixels(self.0, pixels);
+        Ok(())
+    }
+
+    fn clear(&mut self, colour: Self::Color) -> Result<(), Self::Error> {
+        self.0.fill(RGB8::from(colour));
+        Ok(())
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> OriginDimensions for Frame<ROWS, COLS> {
+    fn size(&self) -> Size {
+        size::<ROWS, COLS>()
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> DrawTarget for Frame<ROWS, COLS> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        draw_pixels(self, pixels);
+        Ok(())
+    }
+
+    fn clear(&mut self, colour: Self::Color) -> Result<(), Self::Error> {
+        self.fill(RGB8::from(colour));
+        Ok(())
+    }
+}
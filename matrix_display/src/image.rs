@@ -0,0 +1,96 @@
+//! Images converted to pixels at build time, so nothing needs decoding on the device. The build
+//! script turns each file in `images/` into one of these, stored whichever way takes the least
+//! flash; see [`assets`](super::assets).
+
+use super::{
+    frame_buffer::{Frame, FrameBuffer},
+    rgb8::RGB8,
+};
+
+/// How an image's pixels are stored, all in row-major order
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Pixels {
+    /// Every pixel's colour
+    Raw(&'static [RGB8]),
+    /// The colours the image uses, and an index into them for each pixel
+    Palette {
+        colours: &'static [RGB8],
+        indices: &'static [u8],
+    },
+    /// Runs of pixels of the same colour, as (run length, colour)
+    Rle(&'static [(u8, RGB8)]),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Pixels,
+}
+
+impl Image {
+    /// Every pixel's colour in row-major order, however they're stored
+    pub fn colours(&self) -> Colours {
+        Colours {
+            pixels: self.pixels,
+            index: 0,
+            run: 0,
+        }
+    }
+
+    /// Copy the image into the top left of the buffer, cropping whatever doesn't fit
+    pub fn draw<const ROWS: usize, const COLS: usize>(
+        &self,
+        buffer: &mut impl FrameBuffer<ROWS, COLS>,
+    ) {
+        for (i, colour) in self.colours().enumerate() {
+            let (row, col) = (i / self.width, i % self.width);
+            if row < ROWS && col < COLS {
+                buffer[(row, col)] = colour;
+            }
+        }
+    }
+
+    pub fn to_frame<const ROWS: usize, const COLS: usize>(&self) -> Frame<ROWS, COLS> {
+        let mut frame = Frame::new();
+        self.draw(&mut frame);
+        frame
+    }
+}
+
+/// The colours of an [`Image`], unpacked as they're needed
+pub struct Colours {
+    pixels: Pixels,
+    /// The next pixel, or for [`Pixels::Rle`] the current run
+    index: usize,
+    /// How much of the current run has been used
+    run: u8,
+}
+
+impl Iterator for Colours {
+    type Item = RGB8;
+
+    fn next(&mut self) -> Option<RGB8> {
+        match self.pixels {
+            Pixels::Raw(colours) => {
+                let colour = *colours.get(self.index)?;
+                self.index += 1;
+                Some(colour)
+            }
+            Pixels::Palette { colours, indices } => {
+                let i = *indices.get(self.index)?;
+                self.index += 1;
+                Some(colours[i as usize])
+            }
+            Pixels::Rle(runs) => loop {
+                let (length, colour) = *runs.get(self.index)?;
+                if self.run < length {
+                    self.run += 1;
+                    return Some(colour);
+                }
+                self.index += 1;
+                self.run = 0;
+            },
+        }
+    }
+}
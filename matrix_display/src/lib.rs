@@ -0,0 +1,34 @@
+#![no_std]
+
+pub mod animation;
+pub mod assets;
+pub mod automaton;
+pub mod cake;
+pub mod compositor;
+pub mod correction;
+pub mod font;
+pub mod frame_buffer;
+pub mod game_of_life;
+pub mod graphics;
+pub mod image;
+pub mod marquee;
+pub mod math;
+pub mod matrix_displayer;
+pub mod metaballs;
+pub mod notification;
+pub mod params;
+pub mod pio_emulator;
+pub mod pio_program;
+pub mod pixel_format;
+pub mod pixel_mapper;
+pub mod playlist;
+pub mod power;
+pub mod rgb8;
+pub mod rng;
+pub mod settings;
+pub mod single;
+pub mod timing;
+pub mod transition;
+pub mod wheel;
+pub mod wrap;
+pub mod zones;
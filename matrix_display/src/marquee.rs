@@ -0,0 +1,276 @@
+//! Scrolls a message across the matrix in one of the bitmap [`font`](super::font)s.
+
+use heapless::String;
+
+use super::{
+    font::{Font, FONTS, FONT_5X7},
+    frame_buffer::FrameBuffer,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamValue, Tunable},
+    rgb8::RGB8,
+    wheel::wheel,
+};
+
+/// The longest message, in bytes
+pub const MAX_MESSAGE: usize = 64;
+
+/// Which way the text moves. Text going left or right is one line; going up or down it is
+/// wrapped into lines as wide as the matrix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Scroll {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+const SCROLLS: &[&str] = &["left", "right", "up", "down"];
+
+impl Scroll {
+    fn from_index(i: u8) -> Option<Self> {
+        [Self::Left, Self::Right, Self::Up, Self::Down]
+            .get(i as usize)
+            .copied()
+    }
+
+    fn vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Colouring {
+    /// Everything in the one colour
+    Solid,
+    /// Each character a different colour from the wheel
+    Characters,
+    /// A rainbow running along the direction of scrolling
+    Rainbow,
+}
+
+const COLOURINGS: &[&str] = &["solid", "characters", "rainbow"];
+
+impl Colouring {
+    fn from_index(i: u8) -> Option<Self> {
+        [Self::Solid, Self::Characters, Self::Rainbow]
+            .get(i as usize)
+            .copied()
+    }
+}
+
+const FONT_NAMES: &[&str] = &["3x5", "5x7", "8x8"];
+const MESSAGE: &str = "Hello!";
+const COLOUR: RGB8 = RGB8::new(255, 140, 0);
+const SPEED: f32 = 0.5;
+/// How far round the wheel each character's colour is from the one before
+const CHARACTER_HUE_STEP: usize = 40;
+/// How far round the wheel the rainbow goes per pixel, and moves each frame
+const RAINBOW_HUE_STEP: usize = 8;
+
+pub struct Marquee {
+    message: String<MAX_MESSAGE>,
+    pub font: &'static Font,
+    pub scroll: Scroll,
+    /// How many pixels the text moves each frame
+    pub speed: f32,
+    pub colouring: Colouring,
+    pub colour: RGB8,
+    /// How far the text has moved since it started coming on, in pixels
+    offset: f32,
+    /// How far the rainbow has moved
+    hue: usize,
+}
+
+impl Marquee {
+    pub fn new() -> Self {
+        let mut marquee = Self {
+            message: String::new(),
+            font: &FONT_5X7,
+            scroll: Scroll::Left,
+            speed: SPEED,
+            colouring: Colouring::Solid,
+            colour: COLOUR,
+            offset: 0.0,
+            hue: 0,
+        };
+        marquee.set_message(MESSAGE);
+        marquee
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Show a new message, starting it from the edge of the matrix. Anything past
+    /// [`MAX_MESSAGE`] bytes is cut off.
+    pub fn set_message(&mut self, message: &str) {
+        self.message.clear();
+        for c in message.chars() {
+            if self.message.push(c).is_err() {
+                break;
+            }
+        }
+        self.offset = 0.0;
+    }
+
+    /// How many characters fit on a line when scrolling up or down
+    fn line_length<const COLS: usize>(&self) -> usize {
+        (COLS / self.font.advance).max(1)
+    }
+
+    /// The top left of each character, relative to the top left of the text, with the text's
+    /// width and height
+    fn layout<const COLS: usize>(
+        &self,
+    ) -> (
+        impl Iterator<Item = (char, isize, isize)> + '_,
+        usize,
+        usize,
+    ) {
+        let count = self.message.chars().count();
+        let font = self.font;
+        let (per_line, line_height) = if self.scroll.vertical() {
+            (self.line_length::<COLS>(), font.height + 1)
+        } else {
+            (count.max(1), font.height)
+        };
+        let lines = count.div_ceil(per_line).max(1);
+        let width = per_line.min(count) * font.advance;
+        let positions = self.message.chars().enumerate().map(move |(i, c)| {
+            let (line, column) = (i / per_line, i % per_line);
+            // centre each line of vertical text, including a short last line
+            let in_line = per_line.min(count - line * per_line);
+            let indent = (width - in_line * font.advance) / 2;
+            (
+                c,
+                (indent + column * font.advance) as isize,
+                (line * line_height) as isize,
+            )
+        });
+        (positions, width, lines * line_height)
+    }
+
+    fn colour_at(&self, index: usize, row: isize, col: isize) -> RGB8 {
+        match self.colouring {
+            Colouring::Solid => self.colour,
+            Colouring::Characters => wheel((index * CHARACTER_HUE_STEP) as u8),
+            Colouring::Rainbow => {
+                let along = if self.scroll.vertical() { row } else { col };
+                wheel((along as usize * RAINBOW_HUE_STEP + self.hue) as u8)
+            }
+        }
+    }
+}
+
+impl Default for Marquee {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tunable for Marquee {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "font",
+            kind: ParamKind::Enum(FONT_NAMES),
+            default: ParamValue::Enum(1),
+        },
+        ParamSpec {
+            name: "scroll",
+            kind: ParamKind::Enum(SCROLLS),
+            default: ParamValue::Enum(0),
+        },
+        ParamSpec {
+            name: "speed",
+            kind: ParamKind::Float { min: 0.1, max: 2.0 },
+            default: ParamValue::Float(SPEED),
+        },
+        ParamSpec {
+            name: "colours",
+            kind: ParamKind::Enum(COLOURINGS),
+            default: ParamValue::Enum(0),
+        },
+        ParamSpec {
+            name: "colour",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOUR),
+        },
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            0 => ParamValue::Enum(FONTS.iter().position(|f| *f == self.font)? as u8),
+            1 => ParamValue::Enum(self.scroll as u8),
+            2 => ParamValue::Float(self.speed),
+            3 => ParamValue::Enum(self.colouring as u8),
+            4 => ParamValue::Colour(self.colour),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Enum(i)) => {
+                if let Some(font) = FONTS.get(i as usize) {
+                    self.font = font;
+                }
+            }
+            (1, ParamValue::Enum(i)) => {
+                if let Some(scroll) = Scroll::from_index(i) {
+                    self.scroll = scroll;
+                    self.offset = 0.0;
+                }
+            }
+            (2, ParamValue::Float(speed)) => self.speed = speed,
+            (3, ParamValue::Enum(i)) => {
+                if let Some(colouring) = Colouring::from_index(i) {
+                    self.colouring = colouring;
+                }
+            }
+            (4, ParamValue::Colour(colour)) => self.colour = colour,
+            _ => (),
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> MatrixDisplayer<ROWS, COLS> for Marquee {
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        buffer.clear();
+        let (positions, width, height) = self.layout::<COLS>();
+        // the text comes on at one edge and goes all the way off the other before starting again
+        let (screen, length) = if self.scroll.vertical() {
+            (ROWS, height)
+        } else {
+            (COLS, width)
+        };
+        let travelled = self.offset as isize;
+        let start = match self.scroll {
+            Scroll::Left | Scroll::Up => screen as isize - travelled,
+            Scroll::Right | Scroll::Down => travelled - length as isize,
+        };
+        let (top, left) = if self.scroll.vertical() {
+            (start, 0)
+        } else {
+            (((ROWS as isize) - height as isize) / 2, start)
+        };
+
+        for (i, (c, x, y)) in positions.enumerate() {
+            for (glyph_row, bits) in self.font.glyph(c).iter().enumerate() {
+                for glyph_col in (0..self.font.width).filter(|col| bits & (1 << col) != 0) {
+                    let row = top + y + glyph_row as isize;
+                    let col = left + x + glyph_col as isize;
+                    if (0..ROWS as isize).contains(&row) && (0..COLS as isize).contains(&col) {
+                        buffer[(row as usize, col as usize)] = self.colour_at(i, row, col);
+                    }
+                }
+            }
+        }
+
+        self.offset += self.speed;
+        // the text may have got shorter since, with a new message or font
+        while self.offset >= (screen + length) as f32 {
+            self.offset -= (screen + length) as f32;
+        }
+        self.hue = (self.hue + RAINBOW_HUE_STEP) % 256;
+    }
+}
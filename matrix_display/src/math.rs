@@ -0,0 +1,86 @@
+//! Floating point helpers for the effects. On the RP2040 these are the ROM routines, which are
+//! much faster than the soft-float ones the compiler would otherwise emit. Everywhere else they
+//! are plain `f32` operations with the same signatures and semantics.
+
+#[cfg(feature = "rp2040")]
+pub use embassy_rp::rom_data::float_funcs::{
+    fadd, fcmp, fdiv, fexp, float_to_uint, float_to_uint64, fln, fmul, fsin, fsqrt, fsub,
+    int_to_float,
+};
+
+/// `x` to the power `y`, for `x >= 0`
+#[cfg(feature = "rp2040")]
+pub fn fpow(x: f32, y: f32) -> f32 {
+    if fcmp(x, 0.0) == 0 {
+        0.0
+    } else {
+        fexp(fmul(y, fln(x)))
+    }
+}
+
+#[cfg(not(feature = "rp2040"))]
+pub use host::*;
+
+#[cfg(not(feature = "rp2040"))]
+mod host {
+    pub fn fadd(a: f32, b: f32) -> f32 {
+        a + b
+    }
+
+    pub fn fsub(a: f32, b: f32) -> f32 {
+        a - b
+    }
+
+    pub fn fmul(a: f32, b: f32) -> f32 {
+        a * b
+    }
+
+    pub fn fdiv(n: f32, d: f32) -> f32 {
+        n / d
+    }
+
+    /// Returns -1, 0 or 1 for a < b, a == b and a > b respectively, like the ROM `fcmp`
+    pub fn fcmp(a: f32, b: f32) -> i32 {
+        if a < b {
+            -1
+        } else if a > b {
+            1
+        } else {
+            0
+        }
+    }
+
+    pub fn fsqrt(v: f32) -> f32 {
+        libm::sqrtf(v)
+    }
+
+    pub fn fsin(angle: f32) -> f32 {
+        libm::sinf(angle)
+    }
+
+    pub fn fexp(v: f32) -> f32 {
+        libm::expf(v)
+    }
+
+    pub fn fln(v: f32) -> f32 {
+        libm::logf(v)
+    }
+
+    /// `x` to the power `y`, for `x >= 0`
+    pub fn fpow(x: f32, y: f32) -> f32 {
+        libm::powf(x, y)
+    }
+
+    pub fn int_to_float(v: i32) -> f32 {
+        v as f32
+    }
+
+    /// Truncates towards zero, clamping negative values to 0
+    pub fn float_to_uint(v: f32) -> u32 {
+        v as u32
+    }
+
+    pub fn float_to_uint64(v: f32) -> u64 {
+        v as u64
+    }
+}
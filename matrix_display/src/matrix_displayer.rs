@@ -0,0 +1,237 @@
+use super::{
+    automaton::Automaton,
+    cake::Cake,
+    frame_buffer::FrameBuffer,
+    game_of_life::GameOfLife,
+    marquee::Marquee,
+    metaballs::Metaballs,
+    params::{ParamError, ParamSpec, ParamValue, ParamValues, Tunable, MAX_PARAMS},
+    rng::random_seed,
+    single::Single,
+    wheel::Wheel,
+    wrap::Wrap,
+};
+
+pub trait MatrixDisplayer<const ROWS: usize, const COLS: usize> {
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>);
+    fn iterate(&self) -> impl Iterator<Item = (usize, usize)> {
+        (0..ROWS).flat_map(|r| (0..COLS).map(move |c| (r, c)))
+    }
+}
+
+/// What the rest of the controller needs to know about an effect, to list it and run it
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EffectInfo {
+    pub id: usize,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The time between frames, in ms
+    pub frame_spacing: u64,
+    /// What can be adjusted while the effect runs, from its [`Tunable`] implementation
+    pub params: &'static [ParamSpec],
+}
+
+impl EffectInfo {
+    /// The index and spec of the parameter called `name`
+    pub fn param(&self, name: &str) -> Option<(usize, &'static ParamSpec)> {
+        self.params.iter().enumerate().find(|(_, p)| p.name == name)
+    }
+}
+
+/// Declares every effect in one place, generating the [`Displays`] enum, its dispatch and the
+/// [`EFFECTS`] list the web page and API are built from.
+///
+/// Each effect gives its id, variant and type, then a constructor taking a seed and a way to get
+/// the seed back out, for effects that use random numbers. Every effect type has to implement
+/// [`Tunable`], even if it has no parameters.
+macro_rules! effects {
+    ($(
+        $id:literal => $variant:ident($ty:ty) {
+            name: $name:literal,
+            description: $description:literal,
+            frame_spacing: $frame_spacing:literal,
+            new: $new:expr,
+            seed: $seed:expr $(,)?
+        }
+    ),* $(,)?) => {
+        // there's no heap to box the big ones into, and only one or two displays exist at a time
+        #[allow(clippy::large_enum_variant)]
+        pub enum Displays {
+            $($variant($ty),)*
+        }
+
+        /// Every effect, in id order
+        pub const EFFECTS: &[EffectInfo] = &[
+            $(EffectInfo {
+                id: $id,
+                name: $name,
+                description: $description,
+                frame_spacing: $frame_spacing,
+                params: <$ty as Tunable>::PARAMS,
+            },)*
+        ];
+
+        // ids index into EFFECTS, so they have to count up from 0 in order
+        const _: () = {
+            let mut i = 0;
+            while i < EFFECTS.len() {
+                assert!(EFFECTS[i].id == i, "effect ids must count up from 0");
+                assert!(EFFECTS[i].params.len() <= MAX_PARAMS, "too many parameters");
+                i += 1;
+            }
+        };
+
+        impl Displays {
+            /// Create the display with the given id, seeding its random numbers from `seed` so
+            /// that the same id and seed always play out the same way
+            pub fn with_seed(id: usize, seed: u64) -> Option<Self> {
+                let new: [fn(u64) -> Self; EFFECTS.len()] = [$(|seed| Self::$variant(($new)(seed)),)*];
+                new.get(id).map(|new| new(seed))
+            }
+
+            /// The registry entry for the running display
+            pub fn info(&self) -> &'static EffectInfo {
+                match self {
+                    $(Self::$variant(_) => &EFFECTS[$id],)*
+                }
+            }
+
+            /// The seed the display was created with, if it uses random numbers at all
+            pub fn seed(&self) -> Option<u64> {
+                match self {
+                    $(Self::$variant(display) => ($seed)(display),)*
+                }
+            }
+
+            /// The current value of a parameter, by its index in the effect's
+            /// [`params`](EffectInfo::params)
+            pub fn param(&self, index: usize) -> Option<ParamValue> {
+                match self {
+                    $(Self::$variant(display) => display.param(index),)*
+                }
+            }
+
+            /// Change a parameter, checking the value against its spec and then the effect first
+            pub fn set_param(&mut self, index: usize, value: ParamValue) -> Result<(), ParamError> {
+                let spec = self.info().params.get(index).ok_or(ParamError::Unknown)?;
+                if !spec.accepts(value) {
+                    return Err(ParamError::Invalid);
+                }
+                match self {
+                    $(Self::$variant(display) => {
+                        if !display.check_param(index, value) {
+                            return Err(ParamError::Invalid);
+                        }
+                        display.set_param(index, value);
+                    })*
+                }
+                Ok(())
+            }
+
+            /// Draw the next frame of whichever display is running into the buffer
+            pub fn update<const ROWS: usize, const COLS: usize>(
+                &mut self,
+                buffer: &mut impl FrameBuffer<ROWS, COLS>,
+            ) {
+                match self {
+                    $(Self::$variant(display) => display.update(buffer),)*
+                }
+            }
+        }
+    };
+}
+
+effects! {
+    0 => Wheel(Wheel) {
+        name: "Wheel",
+        description: "A rainbow sweeping across the whole matrix",
+        frame_spacing: 10,
+        new: |_| Wheel(0),
+        seed: |_: &Wheel| None,
+    },
+    1 => Metaballs(Metaballs<10>) {
+        name: "Metaballs",
+        description: "Glowing blobs that drift around and merge",
+        frame_spacing: 50,
+        new: Metaballs::new,
+        seed: |m: &Metaballs<10>| Some(m.seed()),
+    },
+    2 => Cake(Cake<20>) {
+        name: "Cake",
+        description: "A cake with a flickering candle flame",
+        frame_spacing: 50,
+        new: Cake::new,
+        seed: |c: &Cake<20>| Some(c.seed()),
+    },
+    // the board is the size of the matrix, so its edges wrap round on screen
+    3 => GameOfLife(GameOfLife<16, 16>) {
+        name: "Life",
+        description: "Conway's Game of Life, starting again when it dies out or repeats",
+        frame_spacing: 100,
+        new: GameOfLife::new,
+        seed: |g: &GameOfLife<16, 16>| Some(g.seed()),
+    },
+    4 => Automaton(Automaton<16, 16>) {
+        name: "Automaton",
+        description: "Brian's Brain, Wireworld, Langton's ant, HighLife and other cellular automata",
+        frame_spacing: 80,
+        new: Automaton::new,
+        seed: |a: &Automaton<16, 16>| Some(a.seed()),
+    },
+    5 => Single(Single) {
+        name: "Single",
+        description: "One pixel walking over the matrix, for checking the wiring",
+        frame_spacing: 50,
+        new: |_| Single::new(),
+        seed: |_: &Single| None,
+    },
+    6 => Wrap(Wrap) {
+        name: "Wrap",
+        description: "Sine waves cycling through three colours, red, green and blue to start with",
+        frame_spacing: 20,
+        new: |_| Wrap::new(),
+        seed: |_: &Wrap| None,
+    },
+    7 => Marquee(Marquee) {
+        name: "Text",
+        description: "A message scrolling across in a choice of fonts",
+        frame_spacing: 50,
+        new: |_| Marquee::new(),
+        seed: |_: &Marquee| None,
+    },
+}
+
+/// The effect that runs when the controller starts
+pub const DEFAULT_EFFECT: usize = 1;
+
+impl Displays {
+    pub fn frame_spacing(&self) -> u64 {
+        self.info().frame_spacing
+    }
+
+    /// Change the message if this is the text marquee, returning whether it was
+    pub fn set_message(&mut self, message: &str) -> bool {
+        match self {
+            Self::Marquee(marquee) => {
+                marquee.set_message(message);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The current value of every parameter, in order
+    pub fn params(&self) -> ParamValues {
+        (0..self.info().params.len())
+            .filter_map(|i| self.param(i))
+            .collect()
+    }
+}
+
+impl TryFrom<usize> for Displays {
+    type Error = ();
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        Self::with_seed(value, random_seed()).ok_or(())
+    }
+}
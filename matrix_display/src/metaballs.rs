@@ -0,0 +1,221 @@
+use rand::Rng;
+
+use super::frame_buffer::FrameBuffer;
+use super::math as ff;
+use super::matrix_displayer::MatrixDisplayer;
+use super::params::{ParamKind, ParamSpec, ParamValue, Tunable};
+use super::rgb8::RGB8;
+use super::rng::EffectRng;
+
+#[derive(Debug)]
+pub struct Ball {
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+}
+
+const SPEED: f32 = 0.001;
+
+impl Ball {
+    pub fn new(rng: &mut impl Rng, speed: f32) -> Self {
+        let x = ff::fmul(rng.gen(), 16.0);
+        let y = ff::fmul(rng.gen(), 16.0);
+        let vx = ff::fsub(ff::fmul(rng.gen(), speed), ff::fdiv(speed, 2.0));
+        let vy = ff::fsub(ff::fmul(rng.gen(), speed), ff::fdiv(speed, 2.0));
+        Self { x, y, vx, vy }
+    }
+
+    /// Move the ball on, either bouncing off the edges or wrapping round to the other side
+    pub fn update(&mut self, rng: &mut impl Rng, speed: f32, wrap: bool) {
+        let speed_inc = ff::fdiv(speed, 10.0);
+        self.x += self.vx;
+        self.y += self.vy;
+        self.vx = ff::fadd(
+            self.vx,
+            ff::fsub(ff::fmul(rng.gen(), speed_inc), ff::fdiv(speed_inc, 2.0)),
+        );
+        self.vy = ff::fadd(
+            self.vy,
+            ff::fsub(ff::fmul(rng.gen(), speed_inc), ff::fdiv(speed_inc, 2.0)),
+        );
+        if wrap {
+            self.x = wrap_edge(self.x);
+            self.y = wrap_edge(self.y);
+        } else {
+            if ff::fcmp(self.x, 16.0) == 1 {
+                self.x = 16.0;
+                self.vx = -self.vx;
+            } else if ff::fcmp(self.x, 0.0) == -1 {
+                self.x = 0.0;
+                self.vx = -self.vx;
+            }
+            if ff::fcmp(self.y, 16.0) == 1 {
+                self.y = 16.0;
+                self.vy = -self.vy;
+            } else if ff::fcmp(self.y, 0.0) == -1 {
+                self.y = 0.0;
+                self.vy = -self.vy;
+            }
+        }
+        let speed_sq = ff::fadd(ff::fmul(self.vx, self.vx), ff::fmul(self.vy, self.vy));
+        if ff::fcmp(speed_sq, ff::fmul(speed, speed)) == 1 {
+            let speed = ff::fsqrt(speed_sq);
+            self.vx = ff::fdiv(self.vx, speed);
+            self.vy = ff::fdiv(self.vy, speed)
+        }
+    }
+}
+
+fn wrap_edge(v: f32) -> f32 {
+    if ff::fcmp(v, 16.0) == 1 {
+        ff::fsub(v, 16.0)
+    } else if ff::fcmp(v, 0.0) == -1 {
+        ff::fadd(v, 16.0)
+    } else {
+        v
+    }
+}
+
+const WALLS: &[&str] = &["bounce", "wrap"];
+
+#[derive(Debug)]
+pub struct Metaballs<const N: usize> {
+    balls: [Ball; N],
+    rng: EffectRng,
+    /// How many of the balls are drawn
+    count: usize,
+    speed: f32,
+    wrap: bool,
+    /// The colour where the field is weak, which brightens towards `core` as it gets stronger
+    glow: RGB8,
+    core: RGB8,
+}
+
+impl<const N: usize> Metaballs<N> {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = EffectRng::new(seed);
+        Self {
+            balls: [(); N].map(|_| Ball::new(&mut rng, SPEED)),
+            rng,
+            count: N,
+            speed: SPEED,
+            wrap: false,
+            glow: RGB8::new(0, 255, 0),
+            core: RGB8::new(0, 255, 220),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    /// The glow colour dimmed for a field strength up to 2, `level` being out of 255
+    fn dim(&self, level: u32) -> RGB8 {
+        let dim = |g: u8| (g as u32 * level / 255) as u8;
+        RGB8::new(dim(self.glow.r), dim(self.glow.g), dim(self.glow.b))
+    }
+
+    /// Between the glow and core colours for a field strength from 2 to 3, `level` running from
+    /// 110 to 220
+    fn mix(&self, level: u32) -> RGB8 {
+        let mix = |g: u8, c: u8| (g as i32 + (c as i32 - g as i32) * level as i32 / 220) as u8;
+        RGB8::new(
+            mix(self.glow.r, self.core.r),
+            mix(self.glow.g, self.core.g),
+            mix(self.glow.b, self.core.b),
+        )
+    }
+}
+
+impl<const N: usize> Tunable for Metaballs<N> {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "count",
+            kind: ParamKind::Int {
+                min: 1,
+                max: N as i32,
+            },
+            default: ParamValue::Int(N as i32),
+        },
+        ParamSpec {
+            name: "speed",
+            kind: ParamKind::Float {
+                min: 0.0,
+                max: 0.01,
+            },
+            default: ParamValue::Float(SPEED),
+        },
+        ParamSpec {
+            name: "walls",
+            kind: ParamKind::Enum(WALLS),
+            default: ParamValue::Enum(0),
+        },
+        ParamSpec {
+            name: "glow",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(RGB8::new(0, 255, 0)),
+        },
+        ParamSpec {
+            name: "core",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(RGB8::new(0, 255, 220)),
+        },
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            0 => ParamValue::Int(self.count as i32),
+            1 => ParamValue::Float(self.speed),
+            2 => ParamValue::Enum(self.wrap as u8),
+            3 => ParamValue::Colour(self.glow),
+            4 => ParamValue::Colour(self.core),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Int(count)) => self.count = count as usize,
+            (1, ParamValue::Float(speed)) => self.speed = speed,
+            (2, ParamValue::Enum(walls)) => self.wrap = walls == 1,
+            (3, ParamValue::Colour(glow)) => self.glow = glow,
+            (4, ParamValue::Colour(core)) => self.core = core,
+            _ => (),
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const N: usize> MatrixDisplayer<ROWS, COLS>
+    for Metaballs<N>
+{
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        let (rng, speed, wrap) = (&mut self.rng, self.speed, self.wrap);
+        self.balls
+            .iter_mut()
+            .for_each(|i| i.update(rng, speed, wrap));
+        for (row, col) in <Metaballs<N> as MatrixDisplayer<ROWS, COLS>>::iterate(self) {
+            let (frow, fcol) = (ff::int_to_float(row as i32), ff::int_to_float(col as i32));
+            let mut total: f32 = 0.0;
+            for Ball { x, y, .. } in self.balls[..self.count].iter() {
+                let dx = frow - x;
+                let dy = fcol - y;
+                let divisor = ff::fsqrt(ff::fadd(ff::fmul(dx, dx), ff::fmul(dy, dy)));
+                if ff::fcmp(divisor, 0.0) != 0 {
+                    total = ff::fadd(total, ff::fdiv(1.0, divisor));
+                }
+            }
+            buffer[(row, col)] = if total > 1.0 {
+                if total > 3.0 {
+                    self.core
+                } else if total > 2.0 {
+                    self.mix(ff::float_to_uint(ff::fmul(ff::fsub(total, 1.0), 110.0)))
+                } else {
+                    self.dim(ff::float_to_uint(ff::fmul(total, 50.0)))
+                }
+            } else {
+                RGB8::new(0, 0, 0)
+            };
+        }
+    }
+}
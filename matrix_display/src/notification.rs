@@ -0,0 +1,308 @@
+//! Short alerts drawn over whatever display is running, which carries on underneath and is left
+//! exactly as it was when the alert is over. They are drawn over a copy of each frame on its way
+//! out to the LEDs, never into the running display's buffer.
+//!
+//! A notification is written as `style,seconds,priority,rrggbb[,extra]`: `border,5,1,ff0000`
+//! flashes the edge of the matrix, `icon,5,2,00ff00,tick` shows one of the [`Icon`]s and
+//! `text,10,3,ffffff,build failed` scrolls the rest of the line past. Higher priorities go first
+//! and interrupt a lower one that is showing, which comes back for the rest of its time
+//! afterwards.
+
+use core::{fmt, str::FromStr};
+
+use heapless::{String, Vec};
+
+use super::{
+    frame_buffer::{Frame, FrameBuffer},
+    marquee::{Marquee, MAX_MESSAGE},
+    matrix_displayer::MatrixDisplayer,
+    params::parse_colour,
+    rgb8::RGB8,
+};
+
+/// How many notifications can wait behind the one showing
+pub const MAX_PENDING: usize = 8;
+
+/// How long the border stays on, then off, as it flashes
+const FLASH_MS: u32 = 250;
+/// How fast text scrolls, in pixels per ms, whatever the running display's frame spacing
+const TEXT_SPEED: f32 = 0.02;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Icon {
+    Alert,
+    Tick,
+    Cross,
+    Heart,
+}
+
+impl Icon {
+    const NAMES: [(&'static str, Self); 4] = [
+        ("alert", Self::Alert),
+        ("tick", Self::Tick),
+        ("cross", Self::Cross),
+        ("heart", Self::Heart),
+    ];
+
+    fn name(&self) -> &'static str {
+        Self::NAMES.iter().find(|(_, i)| i == self).unwrap().0
+    }
+
+    /// 8x8, with bit 0 of each row its leftmost pixel
+    fn rows(&self) -> [u8; 8] {
+        match self {
+            Self::Alert => [0x18, 0x24, 0x5a, 0x5a, 0x99, 0x81, 0x99, 0xff],
+            Self::Tick => [0x00, 0x80, 0xc0, 0x60, 0x33, 0x1e, 0x0c, 0x00],
+            Self::Cross => [0xc3, 0x66, 0x3c, 0x18, 0x18, 0x3c, 0x66, 0xc3],
+            Self::Heart => [0x66, 0xff, 0xff, 0xff, 0x7e, 0x3c, 0x18, 0x00],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Style {
+    /// Flash a line round the edge of the matrix
+    Border,
+    /// Show an icon in the middle, with the display dimmed behind it
+    Icon(Icon),
+    /// Scroll a message past, with the display dimmed behind it
+    Text(String<MAX_MESSAGE>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub style: Style,
+    pub colour: RGB8,
+    /// How long it shows for
+    pub duration_ms: u32,
+    /// Higher numbers go first
+    pub priority: u8,
+}
+
+impl FromStr for Notification {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(5, ',');
+        let mut field = || fields.next().ok_or(());
+        let (style, seconds, priority, colour) = (field()?, field()?, field()?, field()?);
+        let extra = field();
+        let style = match (style, extra) {
+            ("border", Err(())) => Style::Border,
+            ("icon", Ok(name)) => {
+                let (_, icon) = Icon::NAMES.iter().find(|(n, _)| *n == name).ok_or(())?;
+                Style::Icon(*icon)
+            }
+            ("text", Ok(message)) => Style::Text(message.try_into().map_err(|_| ())?),
+            _ => return Err(()),
+        };
+        let seconds: u32 = seconds.parse().map_err(|_| ())?;
+        Ok(Self {
+            style,
+            colour: parse_colour(colour).ok_or(())?,
+            duration_ms: seconds.checked_mul(1000).ok_or(())?,
+            priority: priority.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = match self.style {
+            Style::Border => "border",
+            Style::Icon(_) => "icon",
+            Style::Text(_) => "text",
+        };
+        let c = self.colour;
+        write!(
+            f,
+            "{},{},{},{:02x}{:02x}{:02x}",
+            style,
+            self.duration_ms / 1000,
+            self.priority,
+            c.r,
+            c.g,
+            c.b
+        )?;
+        match &self.style {
+            Style::Border => Ok(()),
+            Style::Icon(icon) => write!(f, ",{}", icon.name()),
+            Style::Text(message) => write!(f, ",{}", message),
+        }
+    }
+}
+
+/// The notification on screen and how long it has been there
+struct Showing {
+    notification: Notification,
+    elapsed_ms: u32,
+    /// Scrolls the message of a text notification
+    marquee: Option<Marquee>,
+}
+
+impl Showing {
+    fn new(notification: Notification) -> Self {
+        let marquee = match &notification.style {
+            Style::Text(message) => {
+                let mut marquee = Marquee::new();
+                marquee.set_message(message);
+                marquee.colour = notification.colour;
+                Some(marquee)
+            }
+            _ => None,
+        };
+        Self {
+            notification,
+            elapsed_ms: 0,
+            marquee,
+        }
+    }
+}
+
+/// Queues notifications and draws the one showing over each frame of the running display
+pub struct Overlay {
+    /// Highest priority first, and oldest first within a priority
+    pending: Vec<Notification, MAX_PENDING>,
+    showing: Option<Showing>,
+}
+
+impl Overlay {
+    pub const fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            showing: None,
+        }
+    }
+
+    /// Queue a notification behind any others of the same or higher priority. If the queue is
+    /// full the lowest priority one is dropped to make room, or this one is given back if it is
+    /// the lowest.
+    pub fn push(&mut self, notification: Notification) -> Result<(), Notification> {
+        if self.pending.is_full() {
+            match self.pending.last() {
+                Some(last) if last.priority < notification.priority => {
+                    self.pending.pop();
+                }
+                _ => return Err(notification),
+            }
+        }
+        let at = self
+            .pending
+            .iter()
+            .position(|n| n.priority < notification.priority)
+            .unwrap_or(self.pending.len());
+        self.pending.insert(at, notification)
+    }
+
+    /// The notification on screen
+    pub fn showing(&self) -> Option<&Notification> {
+        self.showing.as_ref().map(|s| &s.notification)
+    }
+
+    /// How many notifications are waiting
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether there's anything to draw, or about to be
+    pub fn is_active(&self) -> bool {
+        self.showing.is_some() || !self.pending.is_empty()
+    }
+
+    /// Drop the notification showing and everything waiting
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.showing = None;
+    }
+
+    /// Draw the notification over a frame the running display has just drawn, `elapsed_ms` after
+    /// the last one. Draw onto a copy of the frame rather than the display's own buffer, so the
+    /// notification isn't carried into the display's next frame or the start of a transition.
+    pub fn draw<const ROWS: usize, const COLS: usize>(
+        &mut self,
+        buffer: &mut impl FrameBuffer<ROWS, COLS>,
+        elapsed_ms: u32,
+    ) {
+        self.preempt();
+        if self.showing.is_none() && !self.pending.is_empty() {
+            self.showing = Some(Showing::new(self.pending.remove(0)));
+        }
+        let Some(showing) = &mut self.showing else {
+            return;
+        };
+
+        let colour = showing.notification.colour;
+        match (&showing.notification.style, &mut showing.marquee) {
+            (Style::Border, _) => {
+                if showing.elapsed_ms % (2 * FLASH_MS) < FLASH_MS {
+                    for row in 0..ROWS {
+                        for col in 0..COLS {
+                            if row == 0 || row == ROWS - 1 || col == 0 || col == COLS - 1 {
+                                buffer[(row, col)] = colour;
+                            }
+                        }
+                    }
+                }
+            }
+            (Style::Icon(icon), _) => {
+                dim(buffer);
+                let (top, left) = (ROWS.saturating_sub(8) / 2, COLS.saturating_sub(8) / 2);
+                for (r, bits) in icon.rows().iter().enumerate() {
+                    for c in (0..8).filter(|c| bits & (1 << c) != 0) {
+                        if top + r < ROWS && left + c < COLS {
+                            buffer[(top + r, left + c)] = colour;
+                        }
+                    }
+                }
+            }
+            (Style::Text(_), Some(marquee)) => {
+                dim(buffer);
+                let mut text = Frame::<ROWS, COLS>::new();
+                marquee.speed = elapsed_ms as f32 * TEXT_SPEED;
+                marquee.update(&mut text);
+                for ((row, col), p) in text.pixels() {
+                    if p != RGB8::default() {
+                        buffer[(row, col)] = p;
+                    }
+                }
+            }
+            (Style::Text(_), None) => unreachable!(),
+        }
+
+        showing.elapsed_ms += elapsed_ms;
+        if showing.elapsed_ms >= showing.notification.duration_ms {
+            self.showing = None;
+        }
+    }
+
+    /// Put the notification showing back in the queue if a more important one is waiting, with
+    /// whatever time it had left
+    fn preempt(&mut self) {
+        let more_important = match (&self.showing, self.pending.first()) {
+            (Some(showing), Some(next)) => next.priority > showing.notification.priority,
+            _ => false,
+        };
+        if more_important {
+            let showing = self.showing.take().unwrap();
+            let mut notification = showing.notification;
+            notification.duration_ms -= showing.elapsed_ms;
+            // if the queue is full of more important ones, it's dropped
+            let _ = self.push(notification);
+        }
+    }
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Darken the display behind a notification so it stands out
+fn dim<const ROWS: usize, const COLS: usize>(buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+    for row in 0..ROWS {
+        for col in 0..COLS {
+            buffer[(row, col)] = buffer[(row, col)].half().half();
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Knobs the effects expose so they can be adjusted while they run. Each effect describes its
+//! parameters with a list of [`ParamSpec`]s, and values are checked against the spec before
+//! they reach the effect.
+//!
+//! Values are written as plain numbers, `true`/`false`, `rrggbb` hex for colours and the option
+//! name for enums, e.g. `12`, `0.5`, `ff8000` or `wrap`. Text is taken as it is, up to
+//! [`MAX_TEXT`] bytes, and it's up to the effect to [check](Tunable::check_param) it means
+//! something.
+
+use core::{fmt, str};
+
+use heapless::Vec;
+
+use super::rgb8::RGB8;
+
+/// The most parameters one effect can have
+pub const MAX_PARAMS: usize = 8;
+
+/// The longest a text parameter can be, in bytes
+pub const MAX_TEXT: usize = 28;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ParamKind {
+    Int {
+        min: i32,
+        max: i32,
+    },
+    Float {
+        min: f32,
+        max: f32,
+    },
+    Colour,
+    Bool,
+    /// One of a list of named options
+    Enum(&'static [&'static str]),
+    /// Text the effect reads for itself
+    Text,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ParamValue {
+    Int(i32),
+    Float(f32),
+    Colour(RGB8),
+    Bool(bool),
+    /// The index of the chosen option
+    Enum(u8),
+    Text(ParamText),
+}
+
+/// The value of a text parameter, kept inline so values can be copied around like the others
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct ParamText {
+    len: u8,
+    bytes: [u8; MAX_TEXT],
+}
+
+impl ParamText {
+    const EMPTY: Self = Self {
+        len: 0,
+        bytes: [0; MAX_TEXT],
+    };
+
+    /// The text, if it fits
+    pub const fn new(s: &str) -> Option<Self> {
+        if s.len() > MAX_TEXT {
+            return None;
+        }
+        let mut text = Self::EMPTY;
+        while (text.len as usize) < s.len() {
+            text.bytes[text.len as usize] = s.as_bytes()[text.len as usize];
+            text.len += 1;
+        }
+        Some(text)
+    }
+
+    /// Write out something displayable, if it fits
+    pub fn from_display(value: impl fmt::Display) -> Option<Self> {
+        let mut text = Self::EMPTY;
+        fmt::Write::write_fmt(&mut text, format_args!("{}", value)).ok()?;
+        Some(text)
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only ever filled from whole strs
+        str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for ParamText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let start = self.len as usize;
+        let end = start + s.len();
+        self.bytes
+            .get_mut(start..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end as u8;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ParamText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ParamText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub default: ParamValue,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// The effect has no parameter with that name or index
+    Unknown,
+    /// The value is the wrong type or out of range
+    Invalid,
+}
+
+pub type ParamValues = Vec<ParamValue, MAX_PARAMS>;
+
+/// An effect with parameters that can be changed while it runs
+pub trait Tunable {
+    /// The parameters, in the order their indexes refer to
+    const PARAMS: &'static [ParamSpec] = &[];
+
+    fn param(&self, _index: usize) -> Option<ParamValue> {
+        None
+    }
+
+    /// Whether a value that fits the parameter's spec means something to the effect, for values
+    /// like text that the spec can't check
+    fn check_param(&self, _index: usize, _value: ParamValue) -> bool {
+        true
+    }
+
+    /// Set a parameter to a value that has already been checked against its spec
+    fn set_param(&mut self, _index: usize, _value: ParamValue) {}
+}
+
+impl ParamSpec {
+    /// Whether the value has the right type and is in range
+    pub fn accepts(&self, value: ParamValue) -> bool {
+        match (self.kind, value) {
+            (ParamKind::Int { min, max }, ParamValue::Int(v)) => (min..=max).contains(&v),
+            (ParamKind::Float { min, max }, ParamValue::Float(v)) => (min..=max).contains(&v),
+            (ParamKind::Colour, ParamValue::Colour(_)) => true,
+            (ParamKind::Bool, ParamValue::Bool(_)) => true,
+            (ParamKind::Enum(options), ParamValue::Enum(i)) => (i as usize) < options.len(),
+            (ParamKind::Text, ParamValue::Text(_)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn parse(&self, s: &str) -> Result<ParamValue, ParamError> {
+        let value = match self.kind {
+            ParamKind::Int { .. } => s.parse().map(ParamValue::Int).ok(),
+            ParamKind::Float { .. } => s.parse().map(ParamValue::Float).ok(),
+            ParamKind::Colour => parse_colour(s).map(ParamValue::Colour),
+            ParamKind::Bool => match s {
+                "true" | "1" | "on" => Some(ParamValue::Bool(true)),
+                "false" | "0" | "off" => Some(ParamValue::Bool(false)),
+                _ => None,
+            },
+            ParamKind::Enum(options) => options
+                .iter()
+                .position(|o| *o == s)
+                .map(|i| ParamValue::Enum(i as u8)),
+            ParamKind::Text => ParamText::new(s).map(ParamValue::Text),
+        };
+        value
+            .filter(|v| self.accepts(*v))
+            .ok_or(ParamError::Invalid)
+    }
+
+    /// Show a value the way [`parse`](Self::parse) reads it
+    pub fn show(&self, value: ParamValue) -> impl fmt::Display {
+        ShowParam(self.kind, value)
+    }
+}
+
+pub(crate) fn parse_colour(s: &str) -> Option<RGB8> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(RGB8::new(channel(0)?, channel(2)?, channel(4)?))
+}
+
+struct ShowParam(ParamKind, ParamValue);
+
+impl fmt::Display for ShowParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.0, self.1) {
+            (_, ParamValue::Int(v)) => write!(f, "{}", v),
+            (_, ParamValue::Float(v)) => write!(f, "{}", v),
+            (_, ParamValue::Colour(c)) => write!(f, "{:02x}{:02x}{:02x}", c.r, c.g, c.b),
+            (_, ParamValue::Bool(v)) => write!(f, "{}", v),
+            (ParamKind::Enum(options), ParamValue::Enum(i)) => match options.get(i as usize) {
+                Some(option) => f.write_str(option),
+                None => write!(f, "{}", i),
+            },
+            (_, ParamValue::Enum(i)) => write!(f, "{}", i),
+            (_, ParamValue::Text(text)) => f.write_str(text.as_str()),
+        }
+    }
+}
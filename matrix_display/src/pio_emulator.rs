@@ -0,0 +1,166 @@
+//! A cycle counting emulator for one PIO state machine, covering the instructions the
+//! [`ws2812`](super::pio_program::ws2812) program uses: `out`, `jmp`, `set` and `nop`, with
+//! side-set and delays.
+//!
+//! The state machine drives a single pin and shifts its output left with autopull, as the
+//! firmware configures it. It runs until it stalls waiting for a word, and the pin level is
+//! produced as a run length encoded waveform.
+
+use pio::{
+    Instruction, InstructionOperands, JmpCondition, MovDestination, MovOperation, MovSource,
+    OutDestination, Program, SetDestination,
+};
+
+/// A stretch of time the pin spent at one level
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Level {
+    pub high: bool,
+    pub cycles: u32,
+}
+
+pub struct PioEmulator<'a, const N: usize, I> {
+    program: &'a Program<N>,
+    words: I,
+    /// The shift count at which the output shift register is refilled
+    threshold: u8,
+    pc: u8,
+    x: u32,
+    y: u32,
+    osr: u32,
+    /// How many bits have been shifted out of the OSR, which starts empty
+    shifted: u8,
+    pin: bool,
+    pindir: bool,
+    /// The level being built up, not yet handed out
+    pending: Option<Level>,
+    stalled: bool,
+}
+
+impl<'a, const N: usize, I: Iterator<Item = u32>> PioEmulator<'a, N, I> {
+    /// Run `program` with `words` pushed into the TX FIFO, pulling a new word every `threshold`
+    /// bits
+    pub fn new(program: &'a Program<N>, threshold: u8, words: I) -> Self {
+        Self {
+            program,
+            words,
+            threshold,
+            pc: 0,
+            x: 0,
+            y: 0,
+            osr: 0,
+            shifted: 32,
+            pin: false,
+            pindir: false,
+            pending: None,
+            stalled: false,
+        }
+    }
+
+    /// Execute one instruction, returning the level of the pin and how many cycles it took, or
+    /// `None` if the state machine stalled
+    fn step(&mut self) -> Option<Level> {
+        let code = self.program.code[self.pc as usize];
+        let instruction = Instruction::decode(code, self.program.side_set)
+            .unwrap_or_else(|| panic!("invalid PIO instruction {:#06x}", code));
+
+        // an out stalls on an empty OSR before anything else happens
+        if let InstructionOperands::OUT { .. } = instruction.operands {
+            if self.shifted >= self.threshold {
+                self.osr = self.words.next()?;
+                self.shifted = 0;
+            }
+        }
+
+        if let Some(side_set) = instruction.side_set {
+            self.pin = side_set & 1 == 1;
+        }
+        let level = Level {
+            high: self.pin && self.pindir,
+            cycles: 1 + instruction.delay as u32,
+        };
+
+        let mut next = if self.pc == self.program.wrap.source {
+            self.program.wrap.target
+        } else {
+            self.pc + 1
+        };
+        match instruction.operands {
+            InstructionOperands::JMP { condition, address } => {
+                let jump = match condition {
+                    JmpCondition::Always => true,
+                    JmpCondition::XIsZero => self.x == 0,
+                    JmpCondition::XDecNonZero => {
+                        self.x = self.x.wrapping_sub(1);
+                        self.x.wrapping_add(1) != 0
+                    }
+                    JmpCondition::YIsZero => self.y == 0,
+                    JmpCondition::YDecNonZero => {
+                        self.y = self.y.wrapping_sub(1);
+                        self.y.wrapping_add(1) != 0
+                    }
+                    JmpCondition::XNotEqualY => self.x != self.y,
+                    JmpCondition::OutputShiftRegisterNotEmpty => self.shifted < self.threshold,
+                    JmpCondition::PinHigh => self.pin,
+                };
+                if jump {
+                    next = address;
+                }
+            }
+            InstructionOperands::OUT {
+                destination,
+                bit_count,
+            } => {
+                // a bit count of 0 means 32
+                let bits = if bit_count == 0 { 32 } else { bit_count as u32 };
+                let value = self.osr.checked_shr(32 - bits).unwrap_or(0);
+                self.osr = self.osr.checked_shl(bits).unwrap_or(0);
+                self.shifted = (self.shifted as u32 + bits).min(32) as u8;
+                match destination {
+                    OutDestination::PINS => self.pin = value & 1 == 1,
+                    OutDestination::X => self.x = value,
+                    OutDestination::Y => self.y = value,
+                    OutDestination::NULL => (),
+                    OutDestination::PINDIRS => self.pindir = value & 1 == 1,
+                    _ => panic!("unsupported PIO instruction {:?}", instruction),
+                }
+            }
+            InstructionOperands::SET { destination, data } => match destination {
+                SetDestination::PINS => self.pin = data & 1 == 1,
+                SetDestination::X => self.x = data as u32,
+                SetDestination::Y => self.y = data as u32,
+                SetDestination::PINDIRS => self.pindir = data & 1 == 1,
+            },
+            // nop
+            InstructionOperands::MOV {
+                destination: MovDestination::Y,
+                op: MovOperation::None,
+                source: MovSource::Y,
+            } => (),
+            _ => panic!("unsupported PIO instruction {:?}", instruction),
+        }
+        self.pc = next;
+        Some(level)
+    }
+}
+
+impl<const N: usize, I: Iterator<Item = u32>> Iterator for PioEmulator<'_, N, I> {
+    type Item = Level;
+
+    fn next(&mut self) -> Option<Level> {
+        while !self.stalled {
+            let Some(level) = self.step() else {
+                self.stalled = true;
+                break;
+            };
+            match &mut self.pending {
+                Some(pending) if pending.high == level.high => pending.cycles += level.cycles,
+                pending => {
+                    if let Some(done) = pending.replace(level) {
+                        return Some(done);
+                    }
+                }
+            }
+        }
+        self.pending.take()
+    }
+}
@@ -0,0 +1,38 @@
+//! The PIO program that drives a chain of WS2812 style LEDs, kept here so it can be checked on
+//! the host with the [`pio_emulator`](super::pio_emulator).
+//!
+//! Each bit is an `out` of one bit into X while the line is low for the tail of the previous
+//! bit, a `jmp` that raises the line for the start of the bit, and then either a `jmp` that
+//! keeps it high (a 1) or a `nop` that drops it (a 0). Words are shifted out MSB first with
+//! autopull, see [`PixelFormat::encode`](super::pixel_format::PixelFormat::encode).
+
+use pio::{Assembler, JmpCondition, OutDestination, Program, SetDestination, SideSet};
+
+use super::timing::PioTiming;
+
+pub fn ws2812(timing: &PioTiming) -> Program<32> {
+    let side_set = SideSet::new(false, 1, false);
+    let mut a: Assembler<32> = Assembler::new_with_side_set(side_set);
+
+    let t1 = timing.t1; // start bit
+    let t2 = timing.t2; // data bit
+    let t3 = timing.t3; // stop bit
+
+    let mut wrap_target = a.label();
+    let mut wrap_source = a.label();
+    let mut do_zero = a.label();
+    a.set_with_side_set(SetDestination::PINDIRS, 1, 0);
+    a.bind(&mut wrap_target);
+    // Do stop bit
+    a.out_with_delay_and_side_set(OutDestination::X, 1, t3 - 1, 0);
+    // Do start bit
+    a.jmp_with_delay_and_side_set(JmpCondition::XIsZero, &mut do_zero, t1 - 1, 1);
+    // Do data bit = 1
+    a.jmp_with_delay_and_side_set(JmpCondition::Always, &mut wrap_target, t2 - 1, 1);
+    a.bind(&mut do_zero);
+    // Do data bit = 0
+    a.nop_with_delay_and_side_set(t2 - 1, 0);
+    a.bind(&mut wrap_source);
+
+    a.assemble_with_wrap(wrap_source, wrap_target)
+}
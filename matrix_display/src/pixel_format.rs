@@ -0,0 +1,136 @@
+//! How colours are laid out on the wire for different kinds of LED. A format is written as the
+//! order of the channels, e.g. `grb` for WS2812B or `grbw` for SK6812 RGBW.
+
+use core::{fmt, str::FromStr};
+
+use super::rgb8::RGB8;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColourOrder {
+    Rgb,
+    Rbg,
+    Grb,
+    Gbr,
+    Brg,
+    Bgr,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PixelFormat {
+    pub order: ColourOrder,
+    /// Whether the LEDs have a separate white channel, sent after the colours
+    pub rgbw: bool,
+}
+
+impl PixelFormat {
+    /// WS2812B
+    pub const DEFAULT: Self = Self {
+        order: ColourOrder::Grb,
+        rgbw: false,
+    };
+
+    /// The number of bits sent for each LED
+    pub fn bits(&self) -> u8 {
+        if self.rgbw {
+            32
+        } else {
+            24
+        }
+    }
+
+    /// Make a colour displayable on these LEDs. RGBW LEDs take the part of the colour that all
+    /// three channels share onto the white channel; RGB LEDs mix any white into the colours.
+    pub fn convert(&self, colour: RGB8) -> RGB8 {
+        let RGB8 { r, g, b, w } = colour;
+        if self.rgbw {
+            let white = r.min(g).min(b);
+            RGB8::with_white(r - white, g - white, b - white, w.saturating_add(white))
+        } else {
+            RGB8::new(r.saturating_add(w), g.saturating_add(w), b.saturating_add(w))
+        }
+    }
+
+    /// Pack a converted colour into a word for the PIO, first channel in the top byte
+    pub fn encode(&self, colour: RGB8) -> u32 {
+        let RGB8 { r, g, b, w } = colour;
+        let [c0, c1, c2] = match self.order {
+            ColourOrder::Rgb => [r, g, b],
+            ColourOrder::Rbg => [r, b, g],
+            ColourOrder::Grb => [g, r, b],
+            ColourOrder::Gbr => [g, b, r],
+            ColourOrder::Brg => [b, r, g],
+            ColourOrder::Bgr => [b, g, r],
+        };
+        let c3 = if self.rgbw { w } else { 0 };
+        u32::from_be_bytes([c0, c1, c2, c3])
+    }
+
+    pub fn to_bytes(&self) -> [u8; 2] {
+        [self.order as u8, self.rgbw as u8]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let &[order, rgbw] = bytes else {
+            return None;
+        };
+        let order = match order {
+            0 => ColourOrder::Rgb,
+            1 => ColourOrder::Rbg,
+            2 => ColourOrder::Grb,
+            3 => ColourOrder::Gbr,
+            4 => ColourOrder::Brg,
+            5 => ColourOrder::Bgr,
+            _ => return None,
+        };
+        let rgbw = match rgbw {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        Some(Self { order, rgbw })
+    }
+}
+
+impl Default for PixelFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.order {
+            ColourOrder::Rgb => "rgb",
+            ColourOrder::Rbg => "rbg",
+            ColourOrder::Grb => "grb",
+            ColourOrder::Gbr => "gbr",
+            ColourOrder::Brg => "brg",
+            ColourOrder::Bgr => "bgr",
+        })?;
+        if self.rgbw {
+            f.write_str("w")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PixelFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (order, rgbw) = match s.strip_suffix('w') {
+            Some(order) => (order, true),
+            None => (s, false),
+        };
+        let order = match order {
+            "rgb" => ColourOrder::Rgb,
+            "rbg" => ColourOrder::Rbg,
+            "grb" => ColourOrder::Grb,
+            "gbr" => ColourOrder::Gbr,
+            "brg" => ColourOrder::Brg,
+            "bgr" => ColourOrder::Bgr,
+            _ => return Err(()),
+        };
+        Ok(Self { order, rgbw })
+    }
+}
@@ -0,0 +1,272 @@
+//! Maps a logical (row, col) position onto the position of that LED along the data chain, so the
+//! same effects can drive panels that are wired, rotated or tiled differently.
+//!
+//! A mapper is written as comma separated fields, e.g. `serpentine,top-right,0,none,1x1`:
+//! the wiring within a panel, the corner the chain enters each panel from, the clockwise rotation
+//! in degrees, which axes are mirrored (`none`, `x`, `y` or `xy`), how many panels are tiled down
+//! and across, and optionally the wiring of the chain between panels.
+
+use core::{fmt, str::FromStr};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Wiring {
+    /// Every row runs in the same direction
+    Progressive,
+    /// Alternate rows run in opposite directions
+    Serpentine,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rotation {
+    R0,
+    R90,
+    R180,
+    R270,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PixelMapper {
+    pub wiring: Wiring,
+    pub start: Corner,
+    pub rotation: Rotation,
+    pub mirror_x: bool,
+    pub mirror_y: bool,
+    /// Number of panels stacked vertically
+    pub tiles_down: u8,
+    /// Number of panels side by side
+    pub tiles_across: u8,
+    /// How the chain runs from panel to panel
+    pub tile_wiring: Wiring,
+}
+
+impl PixelMapper {
+    /// A single serpentine panel whose chain starts in the top right, running right to left
+    pub const DEFAULT: Self = Self {
+        wiring: Wiring::Serpentine,
+        start: Corner::TopRight,
+        rotation: Rotation::R0,
+        mirror_x: false,
+        mirror_y: false,
+        tiles_down: 1,
+        tiles_across: 1,
+        tile_wiring: Wiring::Progressive,
+    };
+
+    /// Whether this mapping can be used on a `rows` x `cols` display: the tiles have to divide
+    /// it evenly, and rotating by a quarter turn needs it to be square
+    pub fn fits(&self, rows: usize, cols: usize) -> bool {
+        let (down, across) = (self.tiles_down as usize, self.tiles_across as usize);
+        down > 0
+            && across > 0
+            && rows.is_multiple_of(down)
+            && cols.is_multiple_of(across)
+            && (rows == cols || matches!(self.rotation, Rotation::R0 | Rotation::R180))
+    }
+
+    /// The position along the LED chain of the pixel at (row, col). Only meaningful if the
+    /// mapping [fits](Self::fits) the display.
+    pub fn index<const ROWS: usize, const COLS: usize>(&self, row: usize, col: usize) -> usize {
+        let row = if self.mirror_y { ROWS - 1 - row } else { row };
+        let col = if self.mirror_x { COLS - 1 - col } else { col };
+        let (row, col) = match self.rotation {
+            Rotation::R0 => (row, col),
+            Rotation::R90 => (col, ROWS - 1 - row),
+            Rotation::R180 => (ROWS - 1 - row, COLS - 1 - col),
+            Rotation::R270 => (COLS - 1 - col, row),
+        };
+
+        let (down, across) = (self.tiles_down as usize, self.tiles_across as usize);
+        let (height, width) = (ROWS / down, COLS / across);
+        let (tile_row, tile_col) = (row / height, col / width);
+        let tile_col = match self.tile_wiring {
+            Wiring::Serpentine if tile_row & 1 == 1 => across - 1 - tile_col,
+            _ => tile_col,
+        };
+        let tile = tile_row * across + tile_col;
+
+        let (mut row, mut col) = (row % height, col % width);
+        if matches!(self.start, Corner::BottomLeft | Corner::BottomRight) {
+            row = height - 1 - row;
+        }
+        if matches!(self.start, Corner::TopRight | Corner::BottomRight) {
+            col = width - 1 - col;
+        }
+        if self.wiring == Wiring::Serpentine && row & 1 == 1 {
+            col = width - 1 - col;
+        }
+
+        tile * height * width + row * width + col
+    }
+
+    /// Pack into bytes for storing in flash
+    pub fn to_bytes(&self) -> [u8; 8] {
+        [
+            self.wiring as u8,
+            self.start as u8,
+            self.rotation as u8,
+            self.mirror_x as u8,
+            self.mirror_y as u8,
+            self.tiles_down,
+            self.tiles_across,
+            self.tile_wiring as u8,
+        ]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let wiring = |b| match b {
+            0 => Some(Wiring::Progressive),
+            1 => Some(Wiring::Serpentine),
+            _ => None,
+        };
+        let flag = |b| match b {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        };
+        let &[w, s, r, mx, my, down, across, tw] = bytes else {
+            return None;
+        };
+        Some(Self {
+            wiring: wiring(w)?,
+            start: match s {
+                0 => Corner::TopLeft,
+                1 => Corner::TopRight,
+                2 => Corner::BottomLeft,
+                3 => Corner::BottomRight,
+                _ => return None,
+            },
+            rotation: match r {
+                0 => Rotation::R0,
+                1 => Rotation::R90,
+                2 => Rotation::R180,
+                3 => Rotation::R270,
+                _ => return None,
+            },
+            mirror_x: flag(mx)?,
+            mirror_y: flag(my)?,
+            tiles_down: down,
+            tiles_across: across,
+            tile_wiring: wiring(tw)?,
+        })
+    }
+}
+
+impl Default for PixelMapper {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for Wiring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Wiring::Progressive => "progressive",
+            Wiring::Serpentine => "serpentine",
+        })
+    }
+}
+
+impl FromStr for Wiring {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "progressive" => Ok(Wiring::Progressive),
+            "serpentine" => Ok(Wiring::Serpentine),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for PixelMapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = match self.start {
+            Corner::TopLeft => "top-left",
+            Corner::TopRight => "top-right",
+            Corner::BottomLeft => "bottom-left",
+            Corner::BottomRight => "bottom-right",
+        };
+        let rotation = match self.rotation {
+            Rotation::R0 => 0,
+            Rotation::R90 => 90,
+            Rotation::R180 => 180,
+            Rotation::R270 => 270,
+        };
+        let mirror = match (self.mirror_x, self.mirror_y) {
+            (false, false) => "none",
+            (true, false) => "x",
+            (false, true) => "y",
+            (true, true) => "xy",
+        };
+        write!(
+            f,
+            "{},{},{},{},{}x{},{}",
+            self.wiring,
+            start,
+            rotation,
+            mirror,
+            self.tiles_down,
+            self.tiles_across,
+            self.tile_wiring
+        )
+    }
+}
+
+impl FromStr for PixelMapper {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(',');
+        let mut next = || fields.next().ok_or(());
+        let wiring = next()?.parse()?;
+        let start = match next()? {
+            "top-left" => Corner::TopLeft,
+            "top-right" => Corner::TopRight,
+            "bottom-left" => Corner::BottomLeft,
+            "bottom-right" => Corner::BottomRight,
+            _ => return Err(()),
+        };
+        let rotation = match next()? {
+            "0" => Rotation::R0,
+            "90" => Rotation::R90,
+            "180" => Rotation::R180,
+            "270" => Rotation::R270,
+            _ => return Err(()),
+        };
+        let (mirror_x, mirror_y) = match next()? {
+            "none" => (false, false),
+            "x" => (true, false),
+            "y" => (false, true),
+            "xy" => (true, true),
+            _ => return Err(()),
+        };
+        let (down, across) = next()?.split_once('x').ok_or(())?;
+        let tiles_down = down.parse().map_err(|_| ())?;
+        let tiles_across = across.parse().map_err(|_| ())?;
+        let tile_wiring = match fields.next() {
+            Some(w) => w.parse()?,
+            None => Wiring::Progressive,
+        };
+        if fields.next().is_some() {
+            return Err(());
+        }
+        Ok(Self {
+            wiring,
+            start,
+            rotation,
+            mirror_x,
+            mirror_y,
+            tiles_down,
+            tiles_across,
+            tile_wiring,
+        })
+    }
+}
@@ -0,0 +1,349 @@
+//! Rotates through a list of displays unattended, showing each for its dwell time.
+//!
+//! A playlist is written as a list of `id:dwell seconds[:seed][:params]` entries, optionally
+//! starting with `shuffle`, e.g. `shuffle,0:30,1:60:1234,2:45:life=12;spawn=0.8`. Entries without
+//! a seed get a fresh one every time they come round. The params are `name=value` pairs separated
+//! by `;`, set on the display as its entry starts, with values written the way the
+//! [`params`](super::params) module reads them.
+
+use core::{fmt, str::FromStr};
+
+use heapless::{String, Vec};
+use rand::Rng;
+
+use super::{matrix_displayer::EFFECTS, params::ParamValue, rng::EffectRng};
+
+/// The most entries a playlist can hold
+pub const MAX_ENTRIES: usize = 16;
+
+/// The longest an entry's params can be, written out
+pub const MAX_PARAMS_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The display id, as used by [`Displays::with_seed`](super::matrix_displayer::Displays::with_seed)
+    pub id: u8,
+    pub dwell_s: u16,
+    pub seed: Option<u64>,
+    /// The parameters to set as the entry starts, as written in the playlist, or empty for the
+    /// display's defaults
+    pub params: String<MAX_PARAMS_LEN>,
+}
+
+impl Entry {
+    fn is_valid(&self) -> bool {
+        (self.id as usize) < EFFECTS.len() && self.param_values().all(|p| p.is_some())
+    }
+
+    /// The index and value of each parameter to set, which are all there and valid once the entry
+    /// has been checked
+    pub fn params(&self) -> impl Iterator<Item = (usize, ParamValue)> + '_ {
+        self.param_values().flatten()
+    }
+
+    fn param_values(&self) -> impl Iterator<Item = Option<(usize, ParamValue)>> + '_ {
+        let effect = EFFECTS.get(self.id as usize);
+        self.params
+            .split(';')
+            .filter(|p| !p.is_empty())
+            .map(move |p| {
+                let (name, value) = p.split_once('=')?;
+                let (index, spec) = effect?.param(name)?;
+                Some((index, spec.parse(value).ok()?))
+            })
+    }
+
+    /// The same entry with other params, if they fit and are valid for its display
+    pub fn with_params(&self, params: &str) -> Option<Self> {
+        let entry = Self {
+            params: params.try_into().ok()?,
+            ..self.clone()
+        };
+        entry.is_valid().then_some(entry)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playlist {
+    pub entries: Vec<Entry, MAX_ENTRIES>,
+    /// Play the entries in a random order, shuffled again each time round
+    pub shuffle: bool,
+    /// Whether the playlist is playing, so it carries on after a restart
+    pub enabled: bool,
+}
+
+const HAS_SEED: u8 = 0x80;
+const ENABLED: u8 = 1;
+const SHUFFLE: u8 = 2;
+
+impl Playlist {
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            shuffle: false,
+            enabled: false,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8, { 1 + MAX_ENTRIES * 11 }> {
+        let mut bytes = Vec::new();
+        let flags = if self.enabled { ENABLED } else { 0 } | if self.shuffle { SHUFFLE } else { 0 };
+        bytes.push(flags).unwrap();
+        for entry in &self.entries {
+            let id = entry.id | if entry.seed.is_some() { HAS_SEED } else { 0 };
+            bytes.push(id).unwrap();
+            bytes
+                .extend_from_slice(&entry.dwell_s.to_le_bytes())
+                .unwrap();
+            if let Some(seed) = entry.seed {
+                bytes.extend_from_slice(&seed.to_le_bytes()).unwrap();
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&flags, mut rest) = bytes.split_first()?;
+        let mut playlist = Self {
+            entries: Vec::new(),
+            shuffle: flags & SHUFFLE != 0,
+            enabled: flags & ENABLED != 0,
+        };
+        while let [id, d0, d1, tail @ ..] = rest {
+            let (seed, tail) = if id & HAS_SEED != 0 {
+                let (seed, tail) = tail.split_first_chunk::<8>()?;
+                (Some(u64::from_le_bytes(*seed)), tail)
+            } else {
+                (None, tail)
+            };
+            let entry = Entry {
+                id: id & !HAS_SEED,
+                dwell_s: u16::from_le_bytes([*d0, *d1]),
+                seed,
+                params: String::new(),
+            };
+            if !entry.is_valid() {
+                return None;
+            }
+            playlist.entries.push(entry).ok()?;
+            rest = tail;
+        }
+        rest.is_empty().then_some(playlist)
+    }
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Playlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        if self.shuffle {
+            f.write_str("shuffle")?;
+            separator = ",";
+        }
+        for entry in &self.entries {
+            write!(f, "{}{}:{}", separator, entry.id, entry.dwell_s)?;
+            if let Some(seed) = entry.seed {
+                write!(f, ":{}", seed)?;
+            }
+            if !entry.params.is_empty() {
+                write!(f, ":{}", entry.params)?;
+            }
+            separator = ",";
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Playlist {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut playlist = Self::new();
+        let entries = match s.strip_prefix("shuffle") {
+            Some(rest) => {
+                playlist.shuffle = true;
+                rest.strip_prefix(',').unwrap_or(rest)
+            }
+            None => s,
+        };
+        for entry in entries.split(',').filter(|e| !e.is_empty()) {
+            let mut fields = entry.split(':');
+            let mut next = || fields.next().ok_or(());
+            let id: u8 = next()?.parse().map_err(|_| ())?;
+            let dwell_s = next()?.parse().map_err(|_| ())?;
+            let mut field = fields.next();
+            let seed = match field {
+                Some(seed) if !seed.contains('=') => {
+                    field = fields.next();
+                    Some(seed.parse().map_err(|_| ())?)
+                }
+                _ => None,
+            };
+            if fields.next().is_some() {
+                return Err(());
+            }
+            let entry = Entry {
+                id,
+                dwell_s,
+                seed,
+                params: String::new(),
+            };
+            let entry = entry.with_params(field.unwrap_or("")).ok_or(())?;
+            playlist.entries.push(entry).map_err(|_| ())?;
+        }
+        Ok(playlist)
+    }
+}
+
+/// Where a [`PlaylistPlayer`] is up to, for showing on the web page
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PlaylistStatus {
+    pub running: bool,
+    pub paused: bool,
+    /// The place in the playlist of the entry that is showing
+    pub entry: u8,
+}
+
+impl fmt::Display for PlaylistStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.running, self.paused) {
+            (false, _) => f.write_str("stopped"),
+            (true, false) => write!(f, "playing entry {}", self.entry),
+            (true, true) => write!(f, "paused on entry {}", self.entry),
+        }
+    }
+}
+
+/// Steps through a [`Playlist`], saying which entry to show and when
+pub struct PlaylistPlayer {
+    playlist: Playlist,
+    /// The order the entries are played in this time round, as indexes into the playlist
+    order: Vec<u8, MAX_ENTRIES>,
+    position: usize,
+    elapsed_ms: u32,
+    paused: bool,
+    rng: EffectRng,
+}
+
+impl PlaylistPlayer {
+    /// `seed` decides the shuffled orders
+    pub fn new(seed: u64) -> Self {
+        Self {
+            playlist: Playlist::new(),
+            order: Vec::new(),
+            position: 0,
+            elapsed_ms: 0,
+            paused: false,
+            rng: EffectRng::new(seed),
+        }
+    }
+
+    pub fn playlist(&self) -> &Playlist {
+        &self.playlist
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.playlist.enabled && !self.playlist.entries.is_empty()
+    }
+
+    pub fn status(&self) -> PlaylistStatus {
+        PlaylistStatus {
+            running: self.is_running(),
+            paused: self.paused,
+            entry: self.order.get(self.position).copied().unwrap_or(0),
+        }
+    }
+
+    /// Change the playlist. If it changed and should be playing, it starts again from the top
+    /// and the first entry is returned.
+    pub fn set_playlist(&mut self, playlist: Playlist) -> Option<Entry> {
+        if playlist == self.playlist {
+            return None;
+        }
+        self.playlist = playlist;
+        self.paused = false;
+        self.restart()
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn current(&self) -> Option<Entry> {
+        if !self.is_running() {
+            return None;
+        }
+        let index = *self.order.get(self.position)?;
+        self.playlist.entries.get(index as usize).cloned()
+    }
+
+    /// Skip to the next entry, returning it if the playlist is running
+    pub fn next_entry(&mut self) -> Option<Entry> {
+        if !self.is_running() {
+            return None;
+        }
+        self.elapsed_ms = 0;
+        self.position += 1;
+        if self.position >= self.order.len() {
+            let last = self.order.last().copied();
+            self.shuffle(last);
+            self.position = 0;
+        }
+        self.current()
+    }
+
+    /// Go back to the previous entry, returning it if the playlist is running
+    pub fn previous_entry(&mut self) -> Option<Entry> {
+        if !self.is_running() {
+            return None;
+        }
+        self.elapsed_ms = 0;
+        self.position = self.position.checked_sub(1).unwrap_or(self.order.len() - 1);
+        self.current()
+    }
+
+    /// Let `ms` pass, returning the next entry if the current one's dwell time is up
+    pub fn tick(&mut self, ms: u32) -> Option<Entry> {
+        if self.paused {
+            return None;
+        }
+        let dwell_ms = self.current()?.dwell_s as u32 * 1000;
+        self.elapsed_ms += ms;
+        if self.elapsed_ms < dwell_ms {
+            return None;
+        }
+        self.next_entry()
+    }
+
+    fn restart(&mut self) -> Option<Entry> {
+        self.order = (0..self.playlist.entries.len() as u8).collect();
+        self.shuffle(None);
+        self.position = 0;
+        self.elapsed_ms = 0;
+        self.current()
+    }
+
+    /// Shuffle the order if the playlist asks for it, without playing `last` twice in a row
+    fn shuffle(&mut self, last: Option<u8>) {
+        if !self.playlist.shuffle {
+            return;
+        }
+        for i in (1..self.order.len()).rev() {
+            let j = self.rng.gen_range(0..=i);
+            self.order.swap(i, j);
+        }
+        if self.order.len() > 1 && self.order.first().copied() == last {
+            let j = self.rng.gen_range(1..self.order.len());
+            self.order.swap(0, j);
+        }
+    }
+}
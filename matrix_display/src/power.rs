@@ -0,0 +1,153 @@
+//! Keeps the LEDs inside a current budget. The draw of a frame is estimated from its colours,
+//! and if it is over budget every channel is scaled down by the same factor to fit.
+//!
+//! A budget is written as `budget mA,mA per channel,idle uA per LED`, e.g. `1500,20,1000`.
+
+use core::{fmt, str::FromStr};
+
+use super::rgb8::RGB8;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PowerBudget {
+    /// The most current the LEDs may draw, in mA
+    pub budget_ma: u16,
+    /// The draw of one colour channel at full brightness, in mA
+    pub channel_ma: u8,
+    /// The draw of an LED that is switched off, in uA
+    pub idle_ua: u16,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PowerReport {
+    /// The estimated draw of the frame as the effect drew it, in mA
+    pub requested_ma: u32,
+    /// The estimated draw of the frame that was sent out, in mA
+    pub output_ma: u32,
+    /// Whether the frame had to be scaled down to fit the budget
+    pub limited: bool,
+}
+
+impl PowerBudget {
+    pub const DEFAULT: Self = Self {
+        budget_ma: 1500,
+        channel_ma: 20,
+        idle_ua: 1000,
+    };
+
+    fn active_ua(&self, pixels: &[RGB8]) -> u32 {
+        let levels: u32 = pixels
+            .iter()
+            .map(|p| p.r as u32 + p.g as u32 + p.b as u32 + p.w as u32)
+            .sum();
+        levels * self.channel_ma as u32 * 1000 / 255
+    }
+
+    fn idle_ua(&self, pixels: &[RGB8]) -> u32 {
+        pixels.len() as u32 * self.idle_ua as u32
+    }
+
+    /// Whether the budget covers the draw of this many LEDs switched off, which no amount of
+    /// dimming gets below
+    pub fn fits(&self, leds: usize) -> bool {
+        leds as u64 * self.idle_ua as u64 <= self.budget_ma as u64 * 1000
+    }
+
+    /// The estimated draw of the pixels in mA
+    pub fn estimate_ma(&self, pixels: &[RGB8]) -> u32 {
+        (self.active_ua(pixels) + self.idle_ua(pixels)) / 1000
+    }
+
+    /// Scale the pixels down, if needed, so they fit in the budget
+    pub fn limit(&self, pixels: &mut [RGB8]) -> PowerReport {
+        let active = self.active_ua(pixels);
+        let idle = self.idle_ua(pixels);
+        let budget = self.budget_ma as u32 * 1000;
+        let requested_ma = (active + idle) / 1000;
+        if active + idle <= budget {
+            return PowerReport {
+                requested_ma,
+                output_ma: requested_ma,
+                limited: false,
+            };
+        }
+        if active == 0 {
+            // the LEDs are already off, so there's nothing left to dim
+            return PowerReport {
+                requested_ma,
+                output_ma: requested_ma,
+                limited: true,
+            };
+        }
+
+        // out of 256, rounded down so the result stays under budget
+        let scale = (budget.saturating_sub(idle) as u64 * 256 / active as u64) as u32;
+        for p in pixels.iter_mut() {
+            p.r = (p.r as u32 * scale / 256) as u8;
+            p.g = (p.g as u32 * scale / 256) as u8;
+            p.b = (p.b as u32 * scale / 256) as u8;
+            p.w = (p.w as u32 * scale / 256) as u8;
+        }
+        PowerReport {
+            requested_ma,
+            output_ma: self.estimate_ma(pixels),
+            limited: true,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 5] {
+        let [b0, b1] = self.budget_ma.to_le_bytes();
+        let [i0, i1] = self.idle_ua.to_le_bytes();
+        [b0, b1, self.channel_ma, i0, i1]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let &[b0, b1, channel_ma, i0, i1] = bytes else {
+            return None;
+        };
+        Some(Self {
+            budget_ma: u16::from_le_bytes([b0, b1]),
+            channel_ma,
+            idle_ua: u16::from_le_bytes([i0, i1]),
+        })
+    }
+}
+
+impl Default for PowerBudget {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for PowerBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.budget_ma, self.channel_ma, self.idle_ua)
+    }
+}
+
+impl FromStr for PowerBudget {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(',');
+        let mut next = || fields.next().ok_or(());
+        let budget = Self {
+            budget_ma: next()?.parse().map_err(|_| ())?,
+            channel_ma: next()?.parse().map_err(|_| ())?,
+            idle_ua: next()?.parse().map_err(|_| ())?,
+        };
+        if fields.next().is_some() {
+            return Err(());
+        }
+        Ok(budget)
+    }
+}
+
+impl fmt::Display for PowerReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mA", self.output_ma)?;
+        if self.limited {
+            write!(f, " (limited from {} mA)", self.requested_ma)?;
+        }
+        Ok(())
+    }
+}
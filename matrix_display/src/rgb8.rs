@@ -0,0 +1,38 @@
+use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
+
+/// A colour, with an optional separate white channel for RGBW LEDs. How it is laid out on the
+/// wire is up to the [`PixelFormat`](super::pixel_format::PixelFormat) of the LEDs.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RGB8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub w: u8,
+}
+
+impl From<Rgb888> for RGB8 {
+    fn from(value: Rgb888) -> Self {
+        Self::new(value.r(), value.g(), value.b())
+    }
+}
+
+impl RGB8 {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, w: 0 }
+    }
+
+    pub const fn with_white(r: u8, g: u8, b: u8, w: u8) -> Self {
+        Self { r, g, b, w }
+    }
+
+    /// The same colour at half brightness
+    pub const fn half(self) -> Self {
+        Self::with_white(self.r / 2, self.g / 2, self.b / 2, self.w / 2)
+    }
+}
+
+impl From<(u8, u8, u8)> for RGB8 {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        RGB8::new(r, g, b)
+    }
+}
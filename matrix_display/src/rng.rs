@@ -0,0 +1,89 @@
+//! Random numbers for the effects. Each effect owns an [`EffectRng`] built from a seed, so a run
+//! can be replayed exactly by constructing the effect again with the same seed. The generator is
+//! a plain xorshift, so a seed gives the same frames on the RP2040 and in the simulator.
+
+use rand::{Error, RngCore, SeedableRng};
+
+/// Get a fresh seed from the ring oscillator
+#[cfg(feature = "rp2040")]
+pub fn random_seed() -> u64 {
+    embassy_rp::clocks::RoscRng.next_u64()
+}
+
+/// Get a fresh seed. Without hardware randomness this just steps a global generator, so callers
+/// on the host should pick their own seeds if runs need to differ between processes.
+#[cfg(not(feature = "rp2040"))]
+pub fn random_seed() -> u64 {
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    static STATE: AtomicU64 = AtomicU64::new(0x2545_f491_4f6c_dd1d);
+    splitmix64(STATE.fetch_add(1, Ordering::Relaxed))
+}
+
+pub(crate) fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectRng {
+    seed: u64,
+    state: u64,
+}
+
+impl EffectRng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero, and similar seeds should still give different sequences
+        let state = match splitmix64(seed) {
+            0 => 1,
+            s => s,
+        };
+        Self { seed, state }
+    }
+
+    /// The seed this generator was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for EffectRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for EffectRng {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+
+    fn seed_from_u64(seed: u64) -> Self {
+        Self::new(seed)
+    }
+}
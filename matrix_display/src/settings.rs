@@ -0,0 +1,207 @@
+//! Settings that persist across restarts, and their encoding for storing in flash.
+//!
+//! The encoding is a magic number followed by a list of `[tag, length, data..]` records and a
+//! checksum. Unknown tags are skipped and missing ones keep their defaults, so settings written
+//! by an older or newer firmware still load.
+
+use super::{
+    correction::ColourCorrection,
+    pixel_format::PixelFormat,
+    pixel_mapper::PixelMapper,
+    playlist::{Playlist, MAX_PARAMS_LEN},
+    power::PowerBudget,
+    timing::LedTiming,
+    transition::Transition,
+    zones::Layout,
+};
+
+/// Size of the encoded settings block
+pub const SETTINGS_LEN: usize = 1024;
+
+/// Size of the block before the playlist's params were stored, which older firmware wrote with
+/// its checksum at the end
+const OLD_SETTINGS_LEN: usize = 256;
+
+const MAGIC: [u8; 4] = *b"MXCS";
+const END: u8 = 0xff;
+
+const TAG_MAPPER: u8 = 1;
+const TAG_CORRECTION: u8 = 2;
+const TAG_POWER: u8 = 3;
+const TAG_FORMAT: u8 = 4;
+const TAG_TIMING: u8 = 5;
+const TAG_TRANSITION: u8 = 6;
+const TAG_PLAYLIST: u8 = 7;
+const TAG_ZONES: u8 = 8;
+/// The params of one playlist entry, as its index then the params written out. There's one of
+/// these for each entry that has params, after the playlist.
+const TAG_ENTRY_PARAMS: u8 = 9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub mapper: PixelMapper,
+    /// Gamma in tenths, see [`ColourCorrection`]
+    pub gamma: u8,
+    pub brightness: u8,
+    pub power: PowerBudget,
+    pub format: PixelFormat,
+    pub timing: LedTiming,
+    /// The transition used when switching displays, unless the switch asks for another
+    pub transition: Transition,
+    pub playlist: Playlist,
+    pub zones: Layout,
+}
+
+impl Settings {
+    pub const fn new() -> Self {
+        Self {
+            mapper: PixelMapper::DEFAULT,
+            gamma: ColourCorrection::DEFAULT_GAMMA,
+            brightness: ColourCorrection::DEFAULT_BRIGHTNESS,
+            power: PowerBudget::DEFAULT,
+            format: PixelFormat::DEFAULT,
+            timing: LedTiming::DEFAULT,
+            transition: Transition::DEFAULT,
+            playlist: Playlist::new(),
+            zones: Layout::new(),
+        }
+    }
+
+    pub fn encode(&self) -> [u8; SETTINGS_LEN] {
+        let mut out = Writer {
+            buf: [END; SETTINGS_LEN],
+            len: MAGIC.len(),
+        };
+        out.buf[..MAGIC.len()].copy_from_slice(&MAGIC);
+        out.record(TAG_MAPPER, &self.mapper.to_bytes());
+        out.record(TAG_CORRECTION, &[self.gamma, self.brightness]);
+        out.record(TAG_POWER, &self.power.to_bytes());
+        out.record(TAG_FORMAT, &self.format.to_bytes());
+        out.record(TAG_TIMING, &self.timing.to_bytes());
+        out.record(TAG_TRANSITION, &self.transition.to_bytes());
+        out.record(TAG_PLAYLIST, &self.playlist.to_bytes());
+        for (i, entry) in self.playlist.entries.iter().enumerate() {
+            if !entry.params.is_empty() {
+                let mut data = [0; 1 + MAX_PARAMS_LEN];
+                data[0] = i as u8;
+                data[1..=entry.params.len()].copy_from_slice(entry.params.as_bytes());
+                out.record(TAG_ENTRY_PARAMS, &data[..=entry.params.len()]);
+            }
+        }
+        out.record(TAG_ZONES, &self.zones.to_bytes());
+
+        // the checksum goes in the last byte, after the end marker
+        out.buf[SETTINGS_LEN - 1] = checksum(&out.buf[..SETTINGS_LEN - 1]);
+        out.buf
+    }
+
+    /// Decode settings, falling back to the defaults for anything missing or corrupt
+    pub fn decode(bytes: &[u8]) -> Self {
+        let mut settings = Self::new();
+        if bytes.len() != SETTINGS_LEN || bytes[..MAGIC.len()] != MAGIC {
+            return settings;
+        }
+        let checksummed = |len: usize| checksum(&bytes[..len - 1]) == bytes[len - 1];
+        let Some(len) = [SETTINGS_LEN, OLD_SETTINGS_LEN]
+            .into_iter()
+            .find(|&len| checksummed(len))
+        else {
+            return settings;
+        };
+
+        let mut rest = &bytes[MAGIC.len()..len - 1];
+        while let [tag, len, tail @ ..] = rest {
+            if *tag == END {
+                break;
+            }
+            let Some((data, tail)) = tail.split_at_checked(*len as usize) else {
+                break;
+            };
+            settings.apply(*tag, data);
+            rest = tail;
+        }
+        settings
+    }
+
+    fn apply(&mut self, tag: u8, data: &[u8]) {
+        match (tag, data) {
+            (TAG_MAPPER, _) => {
+                if let Some(mapper) = PixelMapper::from_bytes(data) {
+                    self.mapper = mapper
+                }
+            }
+            (TAG_CORRECTION, &[gamma, brightness]) => {
+                self.gamma = gamma;
+                self.brightness = brightness;
+            }
+            (TAG_POWER, _) => {
+                if let Some(power) = PowerBudget::from_bytes(data) {
+                    self.power = power
+                }
+            }
+            (TAG_FORMAT, _) => {
+                if let Some(format) = PixelFormat::from_bytes(data) {
+                    self.format = format
+                }
+            }
+            (TAG_TIMING, _) => {
+                if let Some(timing) = LedTiming::from_bytes(data) {
+                    self.timing = timing
+                }
+            }
+            (TAG_TRANSITION, _) => {
+                if let Some(transition) = Transition::from_bytes(data) {
+                    self.transition = transition
+                }
+            }
+            (TAG_PLAYLIST, _) => {
+                if let Some(playlist) = Playlist::from_bytes(data) {
+                    self.playlist = playlist
+                }
+            }
+            (TAG_ENTRY_PARAMS, &[index, ref params @ ..]) => {
+                if let Some(entry) = self.playlist.entries.get_mut(index as usize) {
+                    let with_params = core::str::from_utf8(params)
+                        .ok()
+                        .and_then(|params| entry.with_params(params));
+                    if let Some(with_params) = with_params {
+                        *entry = with_params
+                    }
+                }
+            }
+            (TAG_ZONES, _) => {
+                if let Some(zones) = Layout::from_bytes(data) {
+                    self.zones = zones
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Writer {
+    buf: [u8; SETTINGS_LEN],
+    len: usize,
+}
+
+impl Writer {
+    fn record(&mut self, tag: u8, data: &[u8]) {
+        let end = self.len + 2 + data.len();
+        // leave room for the end marker and checksum
+        assert!(end < SETTINGS_LEN - 1, "settings do not fit in flash block");
+        self.buf[self.len] = tag;
+        self.buf[self.len + 1] = data.len() as u8;
+        self.buf[self.len + 2..end].copy_from_slice(data);
+        self.len = end;
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |acc, b| acc.rotate_left(1) ^ b)
+}
@@ -0,0 +1,120 @@
+use super::{
+    frame_buffer::FrameBuffer,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamValue, Tunable},
+    rgb8::RGB8,
+};
+
+/// The order [`Single`] visits the pixels in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WalkOrder {
+    /// Along each row in turn, left to right
+    Rows,
+    /// Along each row, changing direction every row like serpentine wiring
+    Serpentine,
+    /// Down each column in turn
+    Columns,
+}
+
+const ORDERS: &[&str] = &["rows", "serpentine", "columns"];
+
+impl WalkOrder {
+    fn from_index(i: u8) -> Option<Self> {
+        [Self::Rows, Self::Serpentine, Self::Columns]
+            .get(i as usize)
+            .copied()
+    }
+
+    /// The (row, col) of the `n`th pixel of the walk
+    pub fn position<const ROWS: usize, const COLS: usize>(self, n: usize) -> (usize, usize) {
+        match self {
+            Self::Rows => (n / COLS, n % COLS),
+            Self::Serpentine => {
+                let (row, col) = (n / COLS, n % COLS);
+                (row, if row % 2 == 1 { COLS - 1 - col } else { col })
+            }
+            Self::Columns => (n % ROWS, n / ROWS),
+        }
+    }
+}
+
+const COLOUR: RGB8 = RGB8::new(255, 255, 255);
+
+/// A test pattern that lights one pixel at a time, walking over the whole matrix, to check the
+/// wiring and pixel mapping
+pub struct Single {
+    /// How far along the walk the lit pixel is
+    pub position: usize,
+    /// How many pixels the walk moves each frame
+    pub speed: usize,
+    pub colour: RGB8,
+    pub order: WalkOrder,
+}
+
+impl Single {
+    pub const fn new() -> Self {
+        Self {
+            position: 0,
+            speed: 1,
+            colour: COLOUR,
+            order: WalkOrder::Rows,
+        }
+    }
+}
+
+impl Default for Single {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tunable for Single {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "speed",
+            kind: ParamKind::Int { min: 1, max: 16 },
+            default: ParamValue::Int(1),
+        },
+        ParamSpec {
+            name: "colour",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOUR),
+        },
+        ParamSpec {
+            name: "order",
+            kind: ParamKind::Enum(ORDERS),
+            default: ParamValue::Enum(0),
+        },
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            0 => ParamValue::Int(self.speed as i32),
+            1 => ParamValue::Colour(self.colour),
+            2 => ParamValue::Enum(self.order as u8),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Int(speed)) => self.speed = speed as usize,
+            (1, ParamValue::Colour(colour)) => self.colour = colour,
+            (2, ParamValue::Enum(i)) => {
+                if let Some(order) = WalkOrder::from_index(i) {
+                    self.order = order;
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> MatrixDisplayer<ROWS, COLS> for Single {
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        let count = ROWS * COLS;
+        buffer.clear();
+        buffer[self.order.position::<ROWS, COLS>(self.position % count)] = self.colour;
+        self.position = (self.position + self.speed) % count;
+    }
+}
@@ -0,0 +1,177 @@
+//! Bit timings for the different kinds of addressable LED, and the PIO cycle counts that
+//! produce them.
+//!
+//! Every bit starts high, stays high for `t0h` (a 0) or `t1h` (a 1), and then goes low for the
+//! rest of the bit. The PIO program spends `t1` cycles on the common high part, `t2` on the part
+//! that is high only for a 1, and `t3` on the low tail, so the clock divider sets the length of a
+//! cycle and the three counts set the shape.
+//!
+//! A timing is written as the name of a preset, e.g. `ws2811`, or as
+//! `t0h ns,t1h ns,bit ns,reset us`, e.g. `300,600,1250,80`.
+
+use core::{fmt, str::FromStr};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LedTiming {
+    /// How long a 0 bit is high, in ns
+    pub t0h_ns: u16,
+    /// How long a 1 bit is high, in ns
+    pub t1h_ns: u16,
+    /// The length of a whole bit, in ns
+    pub bit_ns: u16,
+    /// How long the line is held low to latch a frame, in us
+    pub reset_us: u16,
+}
+
+/// The cycle counts and clock divider for the PIO program
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PioTiming {
+    pub t1: u8,
+    pub t2: u8,
+    pub t3: u8,
+    /// The clock divider in 1/256ths
+    pub divider: u32,
+}
+
+/// The most cycles one instruction can take, with the delay field shared with one side-set bit
+const MAX_CYCLES: u32 = 16;
+
+impl LedTiming {
+    /// 800kHz. These are the timings the controller has always used, which sit inside the
+    /// tolerances of both the old and new datasheets.
+    pub const WS2812B: Self = Self {
+        t0h_ns: 250,
+        t1h_ns: 875,
+        bit_ns: 1250,
+        reset_us: 280,
+    };
+
+    /// 400kHz
+    pub const WS2811: Self = Self {
+        t0h_ns: 500,
+        t1h_ns: 1200,
+        bit_ns: 2500,
+        reset_us: 280,
+    };
+
+    /// 800kHz with shorter pulses, for SK6812 RGB and RGBW LEDs
+    pub const SK6812: Self = Self {
+        t0h_ns: 300,
+        t1h_ns: 600,
+        bit_ns: 1250,
+        reset_us: 80,
+    };
+
+    pub const DEFAULT: Self = Self::WS2812B;
+
+    const PRESETS: [(&'static str, Self); 3] = [
+        ("ws2812b", Self::WS2812B),
+        ("ws2811", Self::WS2811),
+        ("sk6812", Self::SK6812),
+    ];
+
+    fn is_valid(&self) -> bool {
+        0 < self.t0h_ns && self.t0h_ns < self.t1h_ns && self.t1h_ns < self.bit_ns
+    }
+
+    /// Work out the cycle counts and clock divider that come closest to these timings with a
+    /// system clock of `clock_hz`, or `None` if they can't be reached.
+    pub fn pio_timing(&self, clock_hz: u32) -> Option<PioTiming> {
+        if !self.is_valid() {
+            return None;
+        }
+        let bit = self.bit_ns as u32;
+        let mut best: Option<(u32, PioTiming)> = None;
+        for cycles in 3..=3 * MAX_CYCLES {
+            let high0 = (self.t0h_ns as u32 * cycles + bit / 2) / bit;
+            let high1 = (self.t1h_ns as u32 * cycles + bit / 2) / bit;
+            let (t1, t2, t3) = (
+                high0,
+                high1.saturating_sub(high0),
+                cycles.saturating_sub(high1),
+            );
+            if ![t1, t2, t3].iter().all(|t| (1..=MAX_CYCLES).contains(t)) {
+                continue;
+            }
+            // a divider under 1 would need a faster clock than the system clock
+            let divider = clock_hz as u64 * bit as u64 * 256 / (cycles as u64 * 1_000_000_000);
+            if !(256..=u16::MAX as u64 * 256).contains(&divider) {
+                continue;
+            }
+            let error = (high0 * bit / cycles).abs_diff(self.t0h_ns as u32)
+                + (high1 * bit / cycles).abs_diff(self.t1h_ns as u32);
+            if best.is_none_or(|(best, _)| error < best) {
+                let timing = PioTiming {
+                    t1: t1 as u8,
+                    t2: t2 as u8,
+                    t3: t3 as u8,
+                    divider: divider as u32,
+                };
+                best = Some((error, timing));
+            }
+        }
+        best.map(|(_, timing)| timing)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let [a0, a1] = self.t0h_ns.to_le_bytes();
+        let [b0, b1] = self.t1h_ns.to_le_bytes();
+        let [c0, c1] = self.bit_ns.to_le_bytes();
+        let [d0, d1] = self.reset_us.to_le_bytes();
+        [a0, a1, b0, b1, c0, c1, d0, d1]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let &[a0, a1, b0, b1, c0, c1, d0, d1] = bytes else {
+            return None;
+        };
+        let timing = Self {
+            t0h_ns: u16::from_le_bytes([a0, a1]),
+            t1h_ns: u16::from_le_bytes([b0, b1]),
+            bit_ns: u16::from_le_bytes([c0, c1]),
+            reset_us: u16::from_le_bytes([d0, d1]),
+        };
+        timing.is_valid().then_some(timing)
+    }
+}
+
+impl Default for LedTiming {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for LedTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Self::PRESETS.iter().find(|(_, timing)| timing == self) {
+            Some((name, _)) => f.write_str(name),
+            None => write!(
+                f,
+                "{},{},{},{}",
+                self.t0h_ns, self.t1h_ns, self.bit_ns, self.reset_us
+            ),
+        }
+    }
+}
+
+impl FromStr for LedTiming {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, timing)) = Self::PRESETS.iter().find(|(name, _)| *name == s) {
+            return Ok(*timing);
+        }
+        let mut fields = s.split(',');
+        let mut next = || fields.next().ok_or(());
+        let timing = Self {
+            t0h_ns: next()?.parse().map_err(|_| ())?,
+            t1h_ns: next()?.parse().map_err(|_| ())?,
+            bit_ns: next()?.parse().map_err(|_| ())?,
+            reset_us: next()?.parse().map_err(|_| ())?,
+        };
+        if fields.next().is_some() || !timing.is_valid() {
+            return Err(());
+        }
+        Ok(timing)
+    }
+}
@@ -0,0 +1,279 @@
+//! Blends from one display to the next when switching, so the matrix doesn't jump between
+//! effects. The outgoing and incoming displays keep running side by side, each drawing into its
+//! own frame, while a [`Transition`] mixes the two over its duration.
+//!
+//! A transition is written as `kind,duration ms`, e.g. `crossfade,500` or `iris,1000`. Leaving
+//! out the duration uses the default one, and `cut` switches straight away.
+
+use core::{fmt, mem, str::FromStr};
+
+use super::{
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::Displays,
+    rgb8::RGB8,
+    rng::splitmix64,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// Switch straight away
+    Cut,
+    /// Fade each pixel from the old display to the new one
+    Crossfade,
+    /// Reveal the new display from the right, with the edge moving left
+    WipeLeft,
+    /// Reveal the new display from the left, with the edge moving right
+    WipeRight,
+    /// Swap pixels over one at a time in a scattered order
+    Dissolve,
+    /// Push the old display out to the left with the new one following it in
+    Slide,
+    /// Open a circle showing the new display from the middle outwards
+    Iris,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub duration_ms: u16,
+}
+
+/// `progress` runs from 0, all the old display, to this, all the new one
+pub const PROGRESS_END: u32 = 256;
+
+impl TransitionKind {
+    const NAMES: [(&'static str, Self); 7] = [
+        ("cut", Self::Cut),
+        ("crossfade", Self::Crossfade),
+        ("wipe-left", Self::WipeLeft),
+        ("wipe-right", Self::WipeRight),
+        ("dissolve", Self::Dissolve),
+        ("slide", Self::Slide),
+        ("iris", Self::Iris),
+    ];
+
+    fn name(&self) -> &'static str {
+        Self::NAMES.iter().find(|(_, k)| k == self).unwrap().0
+    }
+}
+
+impl Transition {
+    pub const CUT: Self = Self {
+        kind: TransitionKind::Cut,
+        duration_ms: 0,
+    };
+
+    pub const DEFAULT: Self = Self {
+        kind: TransitionKind::Crossfade,
+        duration_ms: 500,
+    };
+
+    fn is_cut(&self) -> bool {
+        self.kind == TransitionKind::Cut || self.duration_ms == 0
+    }
+
+    /// Draw the mix of `from` and `to` into `out`, `progress` of the way to [`PROGRESS_END`]
+    pub fn blend<const ROWS: usize, const COLS: usize>(
+        &self,
+        progress: u32,
+        from: &Frame<ROWS, COLS>,
+        to: &Frame<ROWS, COLS>,
+        out: &mut impl FrameBuffer<ROWS, COLS>,
+    ) {
+        let progress = progress.min(PROGRESS_END);
+        // how many columns the wipes and slide have covered
+        let edge = (progress as usize * COLS).div_ceil(PROGRESS_END as usize);
+        for row in 0..ROWS {
+            for col in 0..COLS {
+                let (old, new) = (from[(row, col)], to[(row, col)]);
+                out[(row, col)] = match self.kind {
+                    TransitionKind::Cut => new,
+                    TransitionKind::Crossfade => mix(old, new, progress),
+                    TransitionKind::WipeLeft => pick(old, new, col >= COLS - edge),
+                    TransitionKind::WipeRight => pick(old, new, col < edge),
+                    TransitionKind::Dissolve => {
+                        let order = splitmix64((row * COLS + col) as u64) as u8;
+                        pick(old, new, (order as u32) < progress)
+                    }
+                    TransitionKind::Slide => match col + edge {
+                        c if c < COLS => from[(row, c)],
+                        c => to[(row, c - COLS)],
+                    },
+                    TransitionKind::Iris => {
+                        // distances are doubled so the centre can fall between pixels
+                        let dr = (2 * row + 1).abs_diff(ROWS) as u64;
+                        let dc = (2 * col + 1).abs_diff(COLS) as u64;
+                        let corner = ((ROWS - 1).pow(2) + (COLS - 1).pow(2)) as u64;
+                        let radius = progress as u64 * progress as u64 * corner;
+                        let distance = (dr * dr + dc * dc) * (PROGRESS_END as u64).pow(2);
+                        pick(old, new, progress > 0 && distance <= radius)
+                    }
+                };
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 3] {
+        let [d0, d1] = self.duration_ms.to_le_bytes();
+        [self.kind as u8, d0, d1]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let &[kind, d0, d1] = bytes else {
+            return None;
+        };
+        let (_, kind) = TransitionKind::NAMES.get(kind as usize)?;
+        Some(Self {
+            kind: *kind,
+            duration_ms: u16::from_le_bytes([d0, d1]),
+        })
+    }
+}
+
+fn mix(from: RGB8, to: RGB8, progress: u32) -> RGB8 {
+    let channel = |a: u8, b: u8| {
+        ((a as u32 * (PROGRESS_END - progress) + b as u32 * progress) / PROGRESS_END) as u8
+    };
+    RGB8::with_white(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+        channel(from.w, to.w),
+    )
+}
+
+fn pick(from: RGB8, to: RGB8, show_to: bool) -> RGB8 {
+    if show_to {
+        to
+    } else {
+        from
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.kind == TransitionKind::Cut {
+            f.write_str(self.kind.name())
+        } else {
+            write!(f, "{},{}", self.kind.name(), self.duration_ms)
+        }
+    }
+}
+
+impl FromStr for Transition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, duration) = match s.split_once(',') {
+            Some((kind, duration)) => (kind, Some(duration)),
+            None => (s, None),
+        };
+        let (_, kind) = TransitionKind::NAMES
+            .iter()
+            .find(|(name, _)| *name == kind)
+            .ok_or(())?;
+        let duration_ms = match (kind, duration) {
+            (TransitionKind::Cut, None) => 0,
+            (_, None) => Self::DEFAULT.duration_ms,
+            (_, Some(duration)) => duration.parse().map_err(|_| ())?,
+        };
+        Ok(Self {
+            kind: *kind,
+            duration_ms,
+        })
+    }
+}
+
+/// The display on its way out, and how far through the transition it is
+struct Outgoing {
+    display: Displays,
+    transition: Transition,
+    elapsed_ms: u32,
+    /// Whether the old display has taken over what was on the LEDs yet
+    started: bool,
+}
+
+/// Runs the current display, transitioning to a new one whenever it is switched
+pub struct Switcher<const ROWS: usize, const COLS: usize> {
+    current: Displays,
+    outgoing: Option<Outgoing>,
+    from: Frame<ROWS, COLS>,
+    to: Frame<ROWS, COLS>,
+}
+
+impl<const ROWS: usize, const COLS: usize> Switcher<ROWS, COLS> {
+    pub fn new(display: Displays) -> Self {
+        Self {
+            current: display,
+            outgoing: None,
+            from: Frame::new(),
+            to: Frame::new(),
+        }
+    }
+
+    /// The display that is running, or being transitioned to
+    pub fn current(&self) -> &Displays {
+        &self.current
+    }
+
+    /// The current display, for adjusting its parameters. During a transition this is the
+    /// incoming display.
+    pub fn current_mut(&mut self) -> &mut Displays {
+        &mut self.current
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.outgoing.is_some()
+    }
+
+    /// Switch to `display`. Switching again part way through a transition starts a new one from
+    /// the display that was coming in.
+    pub fn switch(&mut self, display: Displays, transition: Transition) {
+        let old = mem::replace(&mut self.current, display);
+        self.outgoing = (!transition.is_cut()).then_some(Outgoing {
+            display: old,
+            transition,
+            elapsed_ms: 0,
+            started: false,
+        });
+    }
+
+    /// The time until the next frame. Both displays run at the new display's pace while they
+    /// are being transitioned between.
+    pub fn frame_spacing(&self) -> u64 {
+        self.current.frame_spacing()
+    }
+
+    /// Draw the next frame into the buffer, `elapsed_ms` after the last one. Transitions run by
+    /// the time passed rather than the frames drawn, so late frames don't stretch them out.
+    pub fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>, elapsed_ms: u32) {
+        let Some(outgoing) = &mut self.outgoing else {
+            self.current.update(buffer);
+            return;
+        };
+        if !outgoing.started {
+            // the old display carries on from what is on the LEDs, and the new one starts blank
+            buffer.pixels().for_each(|(pos, p)| self.from[pos] = p);
+            self.to.clear();
+            outgoing.started = true;
+        }
+        outgoing.display.update(&mut self.from);
+        self.current.update(&mut self.to);
+
+        outgoing.elapsed_ms = outgoing.elapsed_ms.saturating_add(elapsed_ms);
+        let duration = outgoing.transition.duration_ms as u32;
+        let progress = (outgoing.elapsed_ms * PROGRESS_END / duration).min(PROGRESS_END);
+        outgoing
+            .transition
+            .blend(progress, &self.from, &self.to, buffer);
+        if progress == PROGRESS_END {
+            self.outgoing = None;
+        }
+    }
+}
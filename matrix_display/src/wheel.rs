@@ -1,12 +1,14 @@
-use embassy_rp::peripherals::PIO1;
-
-use super::{matrix_displayer::MatrixDisplayer, rgb8::RGB8, ws2812::Ws2812};
+use super::{
+    frame_buffer::FrameBuffer, matrix_displayer::MatrixDisplayer, params::Tunable, rgb8::RGB8,
+};
 
 #[derive(Debug)]
 pub struct Wheel(pub usize);
 
+impl Tunable for Wheel {}
+
 impl<const COLS: usize, const ROWS: usize> MatrixDisplayer<ROWS, COLS> for Wheel {
-    fn update(&mut self, buffer: &mut Ws2812<'_, PIO1, 0, ROWS, COLS>) {
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
         (0..ROWS)
             .flat_map(|r| (0..COLS).map(move |c| (r, c)))
             .enumerate()
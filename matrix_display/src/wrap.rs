@@ -0,0 +1,99 @@
+use super::{
+    frame_buffer::FrameBuffer,
+    math as ff,
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamKind, ParamSpec, ParamValue, Tunable},
+    rgb8::RGB8,
+};
+
+const COLOURS: [RGB8; 3] = [
+    RGB8::new(255, 0, 0),
+    RGB8::new(0, 255, 0),
+    RGB8::new(0, 0, 255),
+];
+
+/// How long the waves stay each colour, in steps
+const PHASE_LEN: usize = 256;
+
+/// Sine waves rippling along the matrix, in each of three colours in turn
+pub struct Wrap {
+    /// How far through the cycle of colours the waves are
+    pub phase: usize,
+    /// How far the waves move each frame
+    pub speed: usize,
+    /// Red, then green, then blue unless changed
+    pub colours: [RGB8; 3],
+}
+
+impl Wrap {
+    pub const fn new() -> Self {
+        Self {
+            phase: 0,
+            speed: 1,
+            colours: COLOURS,
+        }
+    }
+}
+
+impl Default for Wrap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tunable for Wrap {
+    const PARAMS: &'static [ParamSpec] = &[
+        ParamSpec {
+            name: "speed",
+            kind: ParamKind::Int { min: 1, max: 16 },
+            default: ParamValue::Int(1),
+        },
+        ParamSpec {
+            name: "first",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOURS[0]),
+        },
+        ParamSpec {
+            name: "second",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOURS[1]),
+        },
+        ParamSpec {
+            name: "third",
+            kind: ParamKind::Colour,
+            default: ParamValue::Colour(COLOURS[2]),
+        },
+    ];
+
+    fn param(&self, index: usize) -> Option<ParamValue> {
+        Some(match index {
+            0 => ParamValue::Int(self.speed as i32),
+            1..=3 => ParamValue::Colour(self.colours[index - 1]),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, index: usize, value: ParamValue) {
+        match (index, value) {
+            (0, ParamValue::Int(speed)) => self.speed = speed as usize,
+            (1..=3, ParamValue::Colour(colour)) => self.colours[index - 1] = colour,
+            _ => (),
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> MatrixDisplayer<ROWS, COLS> for Wrap {
+    fn update(&mut self, buffer: &mut impl FrameBuffer<ROWS, COLS>) {
+        let colour = self.colours[self.phase / PHASE_LEN];
+        (0..ROWS)
+            .flat_map(|r| (0..COLS).map(move |c| (r, c)))
+            .enumerate()
+            .for_each(|(i, (r, c))| {
+                let wave = ff::fsin(ff::int_to_float((self.phase + i) as i32));
+                let level = ff::float_to_uint64(ff::fmul(ff::fadd(wave, 1.0), 255.0 / 2.0)) as u16;
+                let scale = |channel: u8| (channel as u16 * level / 255) as u8;
+                buffer[(r, c)] = RGB8::new(scale(colour.r), scale(colour.g), scale(colour.b));
+            });
+        self.phase = (self.phase + self.speed) % (COLOURS.len() * PHASE_LEN);
+    }
+}
@@ -0,0 +1,262 @@
+//! Splits the matrix into rectangles each running a display of its own, e.g. Metaballs along the
+//! top half and the wheel along the bottom. Each zone's display draws at its own frame spacing,
+//! and the zones are drawn over whatever is running on the rest of the matrix.
+//!
+//! A layout is written as a list of `id:row:col:rowsxcols` zones, e.g. `1:0:0:8x16,0:8:0:8x16`,
+//! or `none` for no zones. Later zones are drawn over earlier ones, and a layout has to
+//! [fit](Layout::fits) the matrix.
+//!
+//! The zones aren't sub-buffers the size of the zone. The effects are built for a size known when
+//! compiling, so each zone's display draws a full 16x16 frame and the zone is clipped to the top
+//! left of it, making zones up to 16 pixels each way. Effects that fill the frame, like Metaballs
+//! and the wheel, look the same in any zone, but ones that centre or scale to the frame are cut
+//! off rather than fitted: text in an 8 row zone shows only its top half.
+
+use core::{fmt, mem, str::FromStr};
+
+use heapless::Vec;
+
+use super::{
+    compositor::Clock,
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::{Displays, EFFECTS},
+    rng::splitmix64,
+};
+
+/// The most zones a layout can hold
+pub const MAX_ZONES: usize = 4;
+
+/// The most pixels a zone can be each way
+pub const MAX_ZONE_SIZE: u8 = 16;
+
+const FRAME_SIZE: usize = MAX_ZONE_SIZE as usize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// A zone is off the edge of the matrix
+    DoesNotFit,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ZoneSpec {
+    /// The display id, as used by [`Displays::with_seed`]
+    pub id: u8,
+    /// The top left of the zone on the matrix
+    pub row: u8,
+    pub col: u8,
+    /// The size of the zone, up to [`MAX_ZONE_SIZE`] each way
+    pub rows: u8,
+    pub cols: u8,
+}
+
+impl ZoneSpec {
+    fn is_valid(&self) -> bool {
+        (self.id as usize) < EFFECTS.len()
+            && (1..=MAX_ZONE_SIZE).contains(&self.rows)
+            && (1..=MAX_ZONE_SIZE).contains(&self.cols)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub zones: Vec<ZoneSpec, MAX_ZONES>,
+}
+
+impl Layout {
+    pub const fn new() -> Self {
+        Self { zones: Vec::new() }
+    }
+
+    /// Whether every zone is on a matrix of this size
+    pub fn fits(&self, rows: usize, cols: usize) -> bool {
+        self.zones.iter().all(|zone| {
+            zone.row as usize + zone.rows as usize <= rows
+                && zone.col as usize + zone.cols as usize <= cols
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8, { MAX_ZONES * 5 }> {
+        let mut bytes = Vec::new();
+        for zone in &self.zones {
+            let record = [zone.id, zone.row, zone.col, zone.rows, zone.cols];
+            bytes.extend_from_slice(&record).unwrap();
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut layout = Self::new();
+        let mut rest = bytes;
+        while let &[id, row, col, rows, cols, ref tail @ ..] = rest {
+            let zone = ZoneSpec {
+                id,
+                row,
+                col,
+                rows,
+                cols,
+            };
+            if !zone.is_valid() {
+                return None;
+            }
+            layout.zones.push(zone).ok()?;
+            rest = tail;
+        }
+        rest.is_empty().then_some(layout)
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.zones.is_empty() {
+            return f.write_str("none");
+        }
+        let mut separator = "";
+        for zone in &self.zones {
+            write!(
+                f,
+                "{}{}:{}:{}:{}x{}",
+                separator, zone.id, zone.row, zone.col, zone.rows, zone.cols
+            )?;
+            separator = ",";
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Layout {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut layout = Self::new();
+        if s == "none" {
+            return Ok(layout);
+        }
+        for zone in s.split(',') {
+            let mut fields = zone.split(':');
+            let mut next = || fields.next().ok_or(());
+            let (id, row, col, size) = (next()?, next()?, next()?, next()?);
+            let (rows, cols) = size.split_once('x').ok_or(())?;
+            if fields.next().is_some() {
+                return Err(());
+            }
+            let number = |s: &str| s.parse().map_err(|_| ());
+            let zone = ZoneSpec {
+                id: number(id)?,
+                row: number(row)?,
+                col: number(col)?,
+                rows: number(rows)?,
+                cols: number(cols)?,
+            };
+            if !zone.is_valid() {
+                return Err(());
+            }
+            layout.zones.push(zone).map_err(|_| ())?;
+        }
+        Ok(layout)
+    }
+}
+
+/// A display running in part of the matrix, and its last frame
+pub struct Zone {
+    pub display: Displays,
+    pub spec: ZoneSpec,
+    /// Only the top left of this shows, as big as the zone
+    frame: Frame<FRAME_SIZE, FRAME_SIZE>,
+    clock: Clock,
+}
+
+/// Runs the zones of a [`Layout`]
+pub struct Zones {
+    zones: Vec<Zone, MAX_ZONES>,
+}
+
+impl Zones {
+    pub const fn new() -> Self {
+        Self { zones: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    pub fn layout(&self) -> Layout {
+        Layout {
+            zones: self.zones.iter().map(|zone| zone.spec).collect(),
+        }
+    }
+
+    /// Run the zones of `layout`, which has to fit the buffer they're drawn into. A zone whose
+    /// display is already running at the same size keeps running, wherever it has moved to, and
+    /// new ones are seeded from `seed`.
+    pub(crate) fn set_layout(&mut self, layout: &Layout, seed: u64) {
+        let mut old = mem::take(&mut self.zones);
+        for (i, spec) in layout.zones.iter().enumerate() {
+            let same = |zone: &Zone| {
+                zone.display.info().id == spec.id as usize
+                    && (zone.spec.rows, zone.spec.cols) == (spec.rows, spec.cols)
+            };
+            let zone = match old.iter().position(same) {
+                Some(index) => Zone {
+                    spec: *spec,
+                    ..old.swap_remove(index)
+                },
+                None => Zone {
+                    display: Displays::with_seed(
+                        spec.id as usize,
+                        splitmix64(seed.wrapping_add(i as u64)),
+                    )
+                    .unwrap(),
+                    spec: *spec,
+                    frame: Frame::new(),
+                    clock: Clock::DUE,
+                },
+            };
+            // the layout holds no more than the zones have room for
+            let _ = self.zones.push(zone);
+        }
+    }
+
+    /// The time until the next zone is due to draw, if there are any
+    pub fn until_due(&self) -> Option<u64> {
+        self.zones
+            .iter()
+            .map(|zone| zone.clock.until_due(zone.display.frame_spacing()))
+            .min()
+    }
+
+    /// Draw each zone over its part of the buffer, `elapsed_ms` after the last frame. Each
+    /// zone's display only moves on once its own frame spacing has passed.
+    pub(crate) fn update<const ROWS: usize, const COLS: usize>(
+        &mut self,
+        buffer: &mut impl FrameBuffer<ROWS, COLS>,
+        elapsed_ms: u32,
+    ) {
+        for zone in &mut self.zones {
+            if zone.clock.tick(elapsed_ms, zone.display.frame_spacing()) {
+                zone.display.update(&mut zone.frame);
+            }
+            let (top, left) = (zone.spec.row as usize, zone.spec.col as usize);
+            for row in 0..zone.spec.rows as usize {
+                for col in 0..zone.spec.cols as usize {
+                    buffer[(top + row, left + col)] = zone.frame[(row, col)];
+                }
+            }
+        }
+    }
+}
+
+impl Default for Zones {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,88 @@
+use matrix_display::{
+    animation::{Animation, AnimationError, Keyframe, PlayMode},
+    assets::{CAKE_1, CAKE_BARE},
+    frame_buffer::Frame,
+    matrix_displayer::MatrixDisplayer,
+    rgb8::RGB8,
+};
+
+fn keyframes(durations: &[u16]) -> Vec<Keyframe> {
+    durations
+        .iter()
+        .enumerate()
+        .map(|(i, &duration_ms)| Keyframe {
+            image: if i % 2 == 0 { &CAKE_BARE } else { &CAKE_1 },
+            duration_ms,
+        })
+        .collect()
+}
+
+/// The frame index shown at each of `ticks` updates
+fn play(mode: PlayMode, frames: usize, ticks: usize) -> Vec<usize> {
+    let mut animation = Animation::<8>::new(&keyframes(&vec![100; frames]), mode, 50).unwrap();
+    (0..ticks)
+        .map(|_| {
+            let index = animation.index();
+            animation.tick();
+            index
+        })
+        .collect()
+}
+
+#[test]
+fn frames_are_drawn_from_the_images() {
+    let mut animation = Animation::<2>::new(&keyframes(&[50, 50]), PlayMode::Loop, 50).unwrap();
+    let mut frame = Frame::<16, 16>::new();
+    animation.update(&mut frame);
+    let bare = frame[(15, 6)];
+    animation.update(&mut frame);
+    assert_eq!(frame[(15, 6)], RGB8::new(0xff, 0x84, 0));
+    assert_eq!(frame[(14, 6)], RGB8::new(0xff, 0xde, 0));
+    assert_ne!(frame[(15, 6)], bare);
+
+    // smaller buffers get the top left
+    let mut small = Frame::<4, 8>::new();
+    animation.draw(&mut small);
+    animation.draw(&mut frame);
+    for row in 0..4 {
+        for col in 0..8 {
+            assert_eq!(small[(row, col)], frame[(row, col)]);
+        }
+    }
+}
+
+#[test]
+fn modes() {
+    assert_eq!(play(PlayMode::Loop, 3, 8), [0, 0, 1, 1, 2, 2, 0, 0]);
+    assert_eq!(
+        play(PlayMode::PingPong, 3, 12),
+        [0, 0, 1, 1, 2, 2, 1, 1, 0, 0, 1, 1]
+    );
+    assert_eq!(play(PlayMode::Once, 3, 8), [0, 0, 1, 1, 2, 2, 2, 2]);
+    assert_eq!(play(PlayMode::PingPong, 1, 3), [0, 0, 0]);
+}
+
+#[test]
+fn durations_are_per_frame() {
+    let mut animation =
+        Animation::<3>::new(&keyframes(&[50, 150, 20]), PlayMode::Loop, 50).unwrap();
+    let mut shown = Vec::new();
+    for _ in 0..6 {
+        shown.push(animation.index());
+        animation.tick();
+    }
+    // the last frame is shorter than a tick, but every frame is still shown at least once
+    assert_eq!(shown, [0, 1, 1, 1, 2, 0]);
+}
+
+#[test]
+fn bad_keyframes_are_rejected() {
+    assert!(matches!(
+        Animation::<1>::new(&keyframes(&[10, 10]), PlayMode::Loop, 50),
+        Err(AnimationError::TooManyFrames)
+    ));
+    assert!(matches!(
+        Animation::<1>::new(&[], PlayMode::Loop, 50),
+        Err(AnimationError::NoFrames)
+    ));
+}
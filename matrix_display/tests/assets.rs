@@ -0,0 +1,68 @@
+use matrix_display::{
+    assets::{self, CAKE_1, CAKE_BARE, IMAGES},
+    frame_buffer::{Frame, FrameBuffer},
+    image::{Image, Pixels},
+    rgb8::RGB8,
+};
+
+const R: RGB8 = RGB8::new(255, 0, 0);
+const B: RGB8 = RGB8::new(0, 0, 255);
+
+/// The same 3x2 picture stored each way
+const RAW: Image = Image {
+    width: 3,
+    height: 2,
+    pixels: Pixels::Raw(&[R, R, B, B, B, B]),
+};
+const PALETTE: Image = Image {
+    width: 3,
+    height: 2,
+    pixels: Pixels::Palette {
+        colours: &[R, B],
+        indices: &[0, 0, 1, 1, 1, 1],
+    },
+};
+const RLE: Image = Image {
+    width: 3,
+    height: 2,
+    pixels: Pixels::Rle(&[(2, R), (4, B)]),
+};
+
+#[test]
+fn encodings_unpack_the_same() {
+    for image in [RAW, PALETTE, RLE] {
+        assert_eq!(image.colours().collect::<Vec<_>>(), [R, R, B, B, B, B]);
+        let frame = image.to_frame::<4, 4>();
+        assert_eq!(frame[(0, 1)], R);
+        assert_eq!(frame[(1, 2)], B);
+        // outside the image is left alone
+        assert_eq!(frame[(0, 3)], RGB8::default());
+        assert_eq!(frame[(2, 0)], RGB8::default());
+    }
+}
+
+#[test]
+fn images_are_cropped() {
+    let mut frame = Frame::<1, 2>::new();
+    RLE.draw(&mut frame);
+    assert_eq!(frame.pixels().map(|(_, p)| p).collect::<Vec<_>>(), [R, R]);
+}
+
+#[test]
+fn images_are_converted() {
+    assert_eq!((CAKE_1.width, CAKE_1.height), (16, 16));
+    assert_eq!(CAKE_1.colours().count(), 16 * 16);
+    // the flame is only in the lit cake
+    let (lit, bare) = (CAKE_1.to_frame::<16, 16>(), CAKE_BARE.to_frame::<16, 16>());
+    assert_eq!(lit[(15, 6)], RGB8::new(0xff, 0x84, 0));
+    assert_ne!(bare[(15, 6)], lit[(15, 6)]);
+    assert_eq!(bare[(0, 0)], lit[(0, 0)]);
+}
+
+#[test]
+fn images_are_found_by_name() {
+    assert_eq!(IMAGES.len(), 7);
+    assert_eq!(assets::image("cake_bare"), Some(&CAKE_BARE));
+    assert_eq!(assets::image("cake"), None);
+    assert!(assets::animation("cake_1").is_none());
+}
@@ -0,0 +1,145 @@
+use matrix_display::{
+    automaton::{Automaton, Neighbourhood, Rule, Totalistic, PRESETS},
+    frame_buffer::Frame,
+    matrix_displayer::{Displays, MatrixDisplayer, EFFECTS},
+    params::ParamError,
+};
+
+type Board = [[u8; 8]; 8];
+
+fn automaton(rule: Rule) -> Automaton<8, 8> {
+    Automaton::with_rule(1, rule)
+}
+
+fn live(cells: &Board) -> usize {
+    cells.iter().flatten().filter(|&&c| c != 0).count()
+}
+
+#[test]
+fn parse_and_display() {
+    for (name, rule) in PRESETS {
+        assert_eq!(name.parse(), Ok(*rule));
+        assert_eq!(rule.to_string().parse(), Ok(*rule), "{name}");
+    }
+    assert_eq!(Rule::LIFE.to_string(), "B3/S23");
+    assert_eq!(Rule::BRIANS_BRAIN.to_string(), "B2/S/C3");
+    assert_eq!("rule90".parse(), Ok(Rule::Elementary(90)));
+    assert_eq!(
+        "B1/S12/C5V".parse(),
+        Ok(Rule::Totalistic(Totalistic {
+            birth: 0b10,
+            survive: 0b110,
+            states: 5,
+            neighbourhood: Neighbourhood::VonNeumann,
+        }))
+    );
+    assert_eq!("b3/s23".parse(), Ok(Rule::LIFE));
+    assert!("B3".parse::<Rule>().is_err());
+    assert!("B9/S23".parse::<Rule>().is_err());
+    assert!("B5/S23V".parse::<Rule>().is_err());
+    assert!("B3/S23/C1".parse::<Rule>().is_err());
+    assert!("rule256".parse::<Rule>().is_err());
+}
+
+#[test]
+fn elementary_rules_scroll_up() {
+    // rule 90 draws a Sierpinski triangle from a single cell
+    let mut automaton = automaton(Rule::Elementary(90));
+    assert_eq!(automaton.cells()[7], [0, 0, 0, 0, 1, 0, 0, 0]);
+    automaton.step();
+    assert_eq!(automaton.cells()[6], [0, 0, 0, 0, 1, 0, 0, 0]);
+    assert_eq!(automaton.cells()[7], [0, 0, 0, 1, 0, 1, 0, 0]);
+    automaton.step();
+    assert_eq!(automaton.cells()[7], [0, 0, 1, 0, 0, 0, 1, 0]);
+}
+
+#[test]
+fn langtons_ant_turns_and_flips() {
+    let mut automaton = automaton(Rule::LangtonsAnt);
+    let start = automaton.ant();
+    assert_eq!(live(automaton.cells()), 0);
+    // each frame is four moves, each turning right onto an empty cell, so it goes round a square
+    // and ends up back where it started
+    automaton.step();
+    assert_eq!(live(automaton.cells()), 4);
+    assert_eq!(automaton.ant(), start);
+    // now it's on a filled cell, so it clears it and turns left
+    automaton.step();
+    assert_eq!(automaton.cells()[start.row][start.col], 0);
+    assert_eq!(automaton.ant().heading, 2);
+}
+
+#[test]
+fn wireworld_electrons_travel_along_wires() {
+    let mut automaton = automaton(Rule::Wireworld);
+    let head = |a: &Automaton<8, 8>| {
+        (0..8)
+            .flat_map(|r| (0..8).map(move |c| (r, c)))
+            .filter(|&(r, c)| a.cells()[r][c] == 1)
+            .collect::<Vec<_>>()
+    };
+    let [(row, col)] = head(&automaton)[..] else {
+        panic!("expected one electron");
+    };
+    automaton.step();
+    assert!(head(&automaton).contains(&(row, col + 1)));
+    assert_eq!(automaton.cells()[row][col], 2);
+    assert_eq!(automaton.cells()[row][col - 1], 3);
+}
+
+#[test]
+fn generations_cells_die_slowly() {
+    let mut automaton = automaton(Rule::BRIANS_BRAIN);
+    let before = *automaton.cells();
+    automaton.step();
+    // in Brian's Brain every live cell starts dying straight away
+    for (row, cells) in before.iter().enumerate() {
+        for (col, &cell) in cells.iter().enumerate() {
+            match cell {
+                1 => assert_eq!(automaton.cells()[row][col], 2),
+                2 => assert_eq!(automaton.cells()[row][col], 0),
+                _ => (),
+            }
+        }
+    }
+    automaton.update(&mut Frame::<8, 8>::new());
+}
+
+#[test]
+fn rule_parameters_set_any_rule() {
+    let info = EFFECTS.iter().find(|e| e.name == "Automaton").unwrap();
+    let mut display = Displays::with_seed(info.id, 1).unwrap();
+    let (index, spec) = info.param("rule").unwrap();
+    let rule = |display: &Displays| spec.show(display.param(index).unwrap()).to_string();
+    for (name, preset) in PRESETS {
+        display.set_param(index, spec.parse(name).unwrap()).unwrap();
+        assert_eq!(rule(&display), preset.to_string());
+    }
+    display
+        .set_param(index, spec.parse("B36/S23").unwrap())
+        .unwrap();
+    assert_eq!(rule(&display), "B36/S23");
+    for bad in ["B3/S23/C99", "B9/S1", "wireworlds"] {
+        let value = spec.parse(bad).unwrap();
+        assert_eq!(display.set_param(index, value), Err(ParamError::Invalid));
+    }
+    assert_eq!(rule(&display), "B36/S23");
+
+    let (neighbourhood, spec) = info.param("neighbourhood").unwrap();
+    display
+        .set_param(neighbourhood, spec.parse("von-neumann").unwrap())
+        .unwrap();
+    let (states, spec) = info.param("states").unwrap();
+    display.set_param(states, spec.parse("4").unwrap()).unwrap();
+    // six neighbours can't be alive out of four, so that count goes
+    assert_eq!(rule(&display), "B3/S23/C4V");
+    assert_eq!(
+        rule(&display).parse(),
+        Ok(Rule::Totalistic(Totalistic {
+            birth: 1 << 3,
+            survive: 1 << 2 | 1 << 3,
+            states: 4,
+            neighbourhood: Neighbourhood::VonNeumann,
+        }))
+    );
+}
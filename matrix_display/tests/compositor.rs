@@ -0,0 +1,137 @@
+use matrix_display::{
+    compositor::{Blend, Compositor, Stack},
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::Displays,
+    rgb8::RGB8,
+    single::Single,
+    transition::Switcher,
+};
+
+const GREY: RGB8 = RGB8::new(100, 100, 100);
+const ORANGE: RGB8 = RGB8::new(200, 100, 0);
+const BLACK: RGB8 = RGB8::new(0, 0, 0);
+
+#[test]
+fn blend_modes() {
+    let blend = |blend: Blend| blend.apply(GREY, ORANGE, 255);
+    assert_eq!(blend(Blend::Normal), ORANGE);
+    assert_eq!(blend(Blend::Add), RGB8::new(255, 200, 100));
+    assert_eq!(blend(Blend::Multiply), RGB8::new(78, 39, 0));
+    assert_eq!(blend(Blend::Screen), RGB8::new(222, 161, 100));
+    assert_eq!(blend(Blend::Max), RGB8::new(200, 100, 100));
+    // black is see-through on a normal layer
+    assert_eq!(Blend::Normal.apply(GREY, BLACK, 255), GREY);
+    assert_eq!(Blend::Multiply.apply(GREY, BLACK, 255), BLACK);
+}
+
+#[test]
+fn opacity_mixes_with_what_is_below() {
+    assert_eq!(Blend::Normal.apply(GREY, ORANGE, 0), GREY);
+    assert_eq!(
+        Blend::Normal.apply(GREY, ORANGE, 128),
+        RGB8::new(150, 100, 49)
+    );
+    assert_eq!(Blend::Add.apply(GREY, ORANGE, 51), RGB8::new(131, 120, 100));
+}
+
+#[test]
+fn stacks_round_trip() {
+    for s in [
+        "none",
+        "7:screen:200",
+        "0:normal:255,2:multiply:128,5:max:0",
+    ] {
+        let stack: Stack = s.parse().unwrap();
+        assert_eq!(stack.to_string(), s);
+    }
+    let stack: Stack = "7,2:add".parse().unwrap();
+    assert_eq!(stack.to_string(), "7:normal:255,2:add:255");
+
+    for bad in [
+        "",
+        "99",
+        "7:dodge",
+        "7:add:256",
+        "7:add:10:1",
+        "0,0,0,0,0",
+        "7,",
+    ] {
+        assert!(bad.parse::<Stack>().is_err(), "{bad}");
+    }
+}
+
+fn single() -> Switcher<4, 4> {
+    Switcher::new(Displays::Single(Single::new()))
+}
+
+#[test]
+fn without_layers_the_switcher_draws_straight_through() {
+    let (mut a, mut b) = (Frame::<4, 4>::new(), Frame::<4, 4>::new());
+    let (mut with, mut without) = (single(), single());
+    let mut compositor = Compositor::new();
+    for _ in 0..10 {
+        compositor.update(&mut with, &mut a, 50);
+        without.update(&mut b, 50);
+        assert_eq!(a.0, b.0);
+    }
+    assert_eq!(compositor.frame_spacing(&with), 50);
+}
+
+#[test]
+fn layers_run_at_their_own_pace() {
+    let mut switcher = single();
+    let mut compositor = Compositor::new();
+    // the wheel draws every 10ms over the single pixel's 50
+    compositor.set_stack(&"0:add".parse().unwrap(), 1);
+    assert_eq!(compositor.stack().to_string(), "0:add:255");
+    let mut frame = Frame::<4, 4>::new();
+    let position = |switcher: &Switcher<4, 4>| match switcher.current() {
+        Displays::Single(s) => s.position,
+        _ => unreachable!(),
+    };
+
+    compositor.update(&mut switcher, &mut frame, 0);
+    assert_eq!(position(&switcher), 1);
+    assert_eq!(compositor.frame_spacing(&switcher), 10);
+    for _ in 0..4 {
+        compositor.update(&mut switcher, &mut frame, 10);
+    }
+    assert_eq!(position(&switcher), 1);
+    compositor.update(&mut switcher, &mut frame, 10);
+    assert_eq!(position(&switcher), 2);
+}
+
+#[test]
+fn layers_keep_running_when_the_stack_changes() {
+    let mut switcher = single();
+    let mut compositor = Compositor::new();
+    let mut frame = Frame::<4, 4>::new();
+    compositor.set_stack(&"5:normal".parse().unwrap(), 1);
+    for _ in 0..3 {
+        compositor.update(&mut switcher, &mut frame, 50);
+    }
+    compositor.set_stack(&"5:max:100,0".parse().unwrap(), 1);
+    match &compositor.layers()[0].display {
+        Displays::Single(s) => assert_eq!(s.position, 3),
+        _ => unreachable!(),
+    }
+    assert_eq!(compositor.layers()[0].blend, Blend::Max);
+    assert_eq!(compositor.stack().to_string(), "5:max:100,0:normal:255");
+
+    compositor.set_stack(&Stack::new(), 1);
+    assert!(compositor.layers().is_empty());
+}
+
+#[test]
+fn a_normal_layer_shows_the_display_below_through_black() {
+    let mut switcher = Switcher::new(Displays::try_from(0).unwrap());
+    let mut compositor = Compositor::new();
+    compositor.set_stack(&"5".parse().unwrap(), 1);
+    let mut frame = Frame::<4, 4>::new();
+    compositor.update(&mut switcher, &mut frame, 0);
+
+    let mut wheel = Frame::<4, 4>::new();
+    Switcher::new(Displays::try_from(0).unwrap()).update(&mut wheel, 0);
+    let differ = frame.pixels().filter(|(pos, p)| wheel[*pos] != *p).count();
+    assert_eq!(differ, 1);
+}
@@ -0,0 +1,31 @@
+use matrix_display::{correction::ColourCorrection, rgb8::RGB8};
+
+fn channels(p: RGB8) -> (u8, u8, u8) {
+    (p.r, p.g, p.b)
+}
+
+#[test]
+fn linear_at_full_brightness_is_identity() {
+    let correction = ColourCorrection::new(10, 255);
+    for v in 0..=255 {
+        assert_eq!(channels(correction.apply(RGB8::new(v, v, v))), (v, v, v));
+    }
+}
+
+#[test]
+fn brightness_scales_the_top_of_the_range() {
+    let correction = ColourCorrection::new(22, 64);
+    assert_eq!(channels(correction.apply(RGB8::new(255, 0, 0))), (64, 0, 0));
+    assert_eq!(channels(correction.apply(RGB8::new(0, 0, 0))), (0, 0, 0));
+}
+
+#[test]
+fn gamma_darkens_the_midtones_and_is_monotonic() {
+    let correction = ColourCorrection::new(22, 255);
+    let mid = correction.apply(RGB8::new(128, 128, 128)).r;
+    assert!((50..60).contains(&mid), "{mid}");
+    let levels: Vec<u8> = (0..=255)
+        .map(|v| correction.apply(RGB8::new(v, 0, 0)).r)
+        .collect();
+    assert!(levels.windows(2).all(|w| w[0] <= w[1]));
+}
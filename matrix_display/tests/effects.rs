@@ -0,0 +1,29 @@
+use matrix_display::{
+    frame_buffer::Frame,
+    matrix_displayer::{Displays, DEFAULT_EFFECT, EFFECTS},
+};
+
+#[test]
+fn every_effect_can_be_created_and_run() {
+    for effect in EFFECTS {
+        let mut display = Displays::with_seed(effect.id, 1).unwrap();
+        assert_eq!(display.info(), effect);
+        assert_eq!(display.frame_spacing(), effect.frame_spacing);
+        display.update(&mut Frame::<16, 16>::new());
+    }
+    assert!(DEFAULT_EFFECT < EFFECTS.len());
+}
+
+#[test]
+fn unknown_ids_are_rejected() {
+    assert!(Displays::with_seed(EFFECTS.len(), 1).is_none());
+    assert!(Displays::try_from(EFFECTS.len()).is_err());
+}
+
+#[test]
+fn seeded_effects_report_their_seed() {
+    for effect in EFFECTS {
+        let display = Displays::with_seed(effect.id, 1234).unwrap();
+        assert!(matches!(display.seed(), None | Some(1234)), "{}", effect.name);
+    }
+}
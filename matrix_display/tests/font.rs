@@ -0,0 +1,51 @@
+use matrix_display::font::{FONTS, FONT_3X5, FONT_5X7, FONT_8X8};
+
+/// The glyph drawn as rows of `#` and `.`
+fn picture(glyph: &[u8], width: usize) -> Vec<String> {
+    glyph
+        .iter()
+        .map(|row| {
+            (0..width)
+                .map(|col| if row & (1 << col) != 0 { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn glyphs_are_drawn_left_to_right() {
+    assert_eq!(
+        picture(FONT_3X5.glyph('7'), 3),
+        ["###", "..#", ".#.", ".#.", ".#."]
+    );
+    assert_eq!(
+        picture(FONT_5X7.glyph('L'), 5),
+        ["#....", "#....", "#....", "#....", "#....", "####.", "....."]
+    );
+    assert_eq!(picture(FONT_8X8.glyph('_'), 8)[7], "########");
+}
+
+#[test]
+fn every_font_has_every_printable_character() {
+    for font in FONTS {
+        assert!(font.width <= 8 && font.advance >= font.width);
+        for c in ' '..='~' {
+            assert_eq!(font.glyph(c).len(), font.height, "{} {c:?}", font.name);
+        }
+        assert!(font.glyph(' ').iter().all(|&row| row == 0));
+        assert!(font.glyph('A').iter().any(|&row| row != 0));
+        // nothing is drawn outside the glyph
+        for c in ' '..='~' {
+            assert!(font.glyph(c).iter().all(|&row| (row as u16) < 1 << font.width));
+        }
+    }
+}
+
+#[test]
+fn unknown_characters_show_a_question_mark() {
+    for font in FONTS {
+        assert_eq!(font.glyph('é'), font.glyph('?'));
+        assert_eq!(font.glyph('\n'), font.glyph('?'));
+    }
+    assert_eq!(FONT_5X7.text_width("Hi é"), 20);
+}
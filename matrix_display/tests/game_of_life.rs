@@ -0,0 +1,117 @@
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    game_of_life::{GameOfLife, Seeding},
+    matrix_displayer::MatrixDisplayer,
+};
+
+type Board = [[bool; 8]; 8];
+
+fn board(live: &[(usize, usize)]) -> Board {
+    let mut cells = [[false; 8]; 8];
+    for &(r, c) in live {
+        cells[r][c] = true;
+    }
+    cells
+}
+
+fn live(cells: &Board) -> Vec<(usize, usize)> {
+    (0..8)
+        .flat_map(|r| (0..8).map(move |c| (r, c)))
+        .filter(|&(r, c)| cells[r][c])
+        .collect()
+}
+
+#[test]
+fn gliders_wrap_round_the_edges() {
+    let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    let mut life = GameOfLife::from_cells(board(&glider), 1);
+    // after 4 generations a glider has moved one cell down and right
+    for _ in 0..4 {
+        life.step();
+    }
+    let moved: Vec<_> = glider.iter().map(|&(r, c)| (r + 1, c + 1)).collect();
+    assert_eq!(live(life.cells()), moved);
+
+    // so after 24 more it has crossed the bottom right corner
+    for _ in 0..24 {
+        life.step();
+    }
+    let mut wrapped: Vec<_> = glider
+        .iter()
+        .map(|&(r, c)| ((r + 7) % 8, (c + 7) % 8))
+        .collect();
+    wrapped.sort();
+    assert_eq!(live(life.cells()), wrapped);
+}
+
+#[test]
+fn dead_boards_are_reseeded() {
+    let mut life = GameOfLife::from_cells(board(&[(3, 3)]), 1);
+    life.step();
+    assert!(!live(life.cells()).is_empty());
+}
+
+#[test]
+fn cycles_are_reseeded() {
+    let blinker = board(&[(3, 2), (3, 3), (3, 4)]);
+    let mut life = GameOfLife::from_cells(blinker, 1);
+    life.step();
+    assert_eq!(live(life.cells()), [(2, 3), (3, 3), (4, 3)]);
+    // back to where it started, which is a repeat
+    life.step();
+    assert_ne!(life.cells(), &blinker);
+
+    let block = board(&[(1, 1), (1, 2), (2, 1), (2, 2)]);
+    let mut life = GameOfLife::from_cells(block, 1);
+    life.step();
+    assert_ne!(life.cells(), &block);
+}
+
+#[test]
+fn patterns_are_placed_whole() {
+    for (seeding, cells) in [
+        (Seeding::Glider, 5),
+        (Seeding::RPentomino, 5),
+        (Seeding::Acorn, 7),
+        (Seeding::Spaceship, 9),
+    ] {
+        for seed in 0..10 {
+            let life = GameOfLife::<8, 8>::with_seeding(seed, seeding);
+            assert_eq!(live(life.cells()).len(), cells, "{seeding:?}");
+        }
+    }
+}
+
+#[test]
+fn cells_are_coloured_by_age() {
+    // a block that never changes, and a glider whose cells keep being born
+    let mut cells = [[false; 16]; 16];
+    for (r, c) in [
+        (1, 1),
+        (1, 2),
+        (2, 1),
+        (2, 2),
+        (8, 9),
+        (9, 10),
+        (10, 8),
+        (10, 9),
+        (10, 10),
+    ] {
+        cells[r][c] = true;
+    }
+    let mut life = GameOfLife::from_cells(cells, 1);
+    let mut frame = Frame::<16, 16>::new();
+    for _ in 0..5 {
+        life.update(&mut frame);
+    }
+    let old = frame[(1, 1)];
+    assert_ne!(old, Default::default());
+    let lit: Vec<_> = frame
+        .pixels()
+        .filter(|&((r, _), p)| r > 4 && p != Default::default())
+        .map(|(_, p)| p)
+        .collect();
+    assert_eq!(lit.len(), 5);
+    assert!(lit.iter().any(|&p| p != old));
+    assert_eq!(frame[(0, 0)], Default::default());
+}
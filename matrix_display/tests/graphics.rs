@@ -0,0 +1,43 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::Rgb888,
+    Drawable, Pixel,
+};
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    graphics::Canvas,
+};
+
+#[test]
+fn points_map_to_row_and_column() {
+    let mut frame = Frame::<4, 8>::new();
+    assert_eq!(frame.bounding_box().size, Size::new(8, 4));
+
+    Pixel(Point::new(5, 2), Rgb888::new(1, 2, 3))
+        .draw(&mut frame)
+        .unwrap();
+    let p = frame[(2, 5)];
+    assert_eq!((p.r, p.g, p.b), (1, 2, 3));
+    assert_eq!(frame.pixels().filter(|(_, p)| p.r != 0).count(), 1);
+}
+
+#[test]
+fn out_of_bounds_pixels_are_clipped() {
+    let mut frame = Frame::<4, 8>::new();
+    frame
+        .draw_iter([
+            Pixel(Point::new(-1, 0), Rgb888::new(255, 0, 0)),
+            Pixel(Point::new(0, 4), Rgb888::new(255, 0, 0)),
+            Pixel(Point::new(8, 0), Rgb888::new(255, 0, 0)),
+        ])
+        .unwrap();
+    assert!(frame.pixels().all(|(_, p)| p.r == 0));
+}
+
+#[test]
+fn canvas_draws_into_any_frame_buffer() {
+    let mut frame = Frame::<4, 8>::new();
+    Canvas(&mut frame).clear(Rgb888::new(0, 9, 0)).unwrap();
+    assert!(frame.pixels().all(|(_, p)| p.g == 9));
+}
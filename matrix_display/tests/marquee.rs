@@ -0,0 +1,106 @@
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    marquee::{Colouring, Marquee, Scroll, MAX_MESSAGE},
+    matrix_displayer::MatrixDisplayer,
+    params::{ParamValue, Tunable},
+    rgb8::RGB8,
+};
+
+fn lit(frame: &Frame<16, 16>) -> Vec<(usize, usize)> {
+    frame
+        .pixels()
+        .filter(|(_, p)| *p != RGB8::default())
+        .map(|(at, _)| at)
+        .collect()
+}
+
+/// Run until the text has moved `pixels` along
+fn scrolled(marquee: &mut Marquee, pixels: usize) -> Frame<16, 16> {
+    marquee.speed = 1.0;
+    let mut frame = Frame::new();
+    for _ in 0..=pixels {
+        marquee.update(&mut frame);
+    }
+    frame
+}
+
+#[test]
+fn text_comes_on_from_the_right() {
+    let mut marquee = Marquee::new();
+    marquee.set_message("I");
+    let mut frame = Frame::new();
+    marquee.update(&mut frame);
+    assert!(lit(&frame).is_empty());
+
+    // the 5x7 I is three pixels wide, one in from the left of its glyph
+    let frame = scrolled(&mut marquee, 4);
+    let columns: Vec<usize> = lit(&frame).iter().map(|&(_, col)| col).collect();
+    assert_eq!(columns.iter().min(), Some(&13));
+    assert_eq!(columns.iter().max(), Some(&15));
+    // centred on the 16 rows
+    let rows: Vec<usize> = lit(&frame).iter().map(|&(row, _)| row).collect();
+    assert_eq!((rows.iter().min(), rows.iter().max()), (Some(&4), Some(&9)));
+}
+
+#[test]
+fn text_goes_all_the_way_off_before_starting_again() {
+    let mut marquee = Marquee::new();
+    marquee.set_message("I");
+    marquee.speed = 1.0;
+    let mut frame = Frame::new();
+    let mut frames: Vec<Vec<(usize, usize)>> = Vec::new();
+    for _ in 0..42 {
+        marquee.update(&mut frame);
+        frames.push(lit(&frame));
+    }
+    // 16 columns of matrix and 5 of text
+    assert!(frames[20].is_empty());
+    assert_eq!(frames[..21], frames[21..]);
+}
+
+#[test]
+fn text_scrolls_the_other_ways() {
+    let mut marquee = Marquee::new();
+    marquee.set_message("I");
+    marquee.scroll = Scroll::Right;
+    let frame = scrolled(&mut marquee, 4);
+    assert!(lit(&frame).iter().all(|&(_, col)| col < 4));
+
+    // upwards the text is wrapped into lines, each one centred
+    let mut marquee = Marquee::new();
+    marquee.set_message("IIII");
+    marquee.set_param(1, ParamValue::Enum(2));
+    assert_eq!(marquee.scroll, Scroll::Up);
+    let frame = scrolled(&mut marquee, 16);
+    let lit = lit(&frame);
+    // three characters on the first line, one on the second
+    assert_eq!(lit.iter().filter(|&&(row, _)| row == 0).count(), 9);
+    assert_eq!(lit.iter().filter(|&&(row, _)| row == 8).count(), 3);
+    assert!(lit.contains(&(8, 6)) && lit.contains(&(8, 8)));
+}
+
+#[test]
+fn colours() {
+    let mut marquee = Marquee::new();
+    marquee.set_message("II");
+    marquee.colour = RGB8::new(1, 2, 3);
+    let frame = scrolled(&mut marquee, 10);
+    assert!(frame
+        .pixels()
+        .all(|(_, p)| p == RGB8::default() || p == RGB8::new(1, 2, 3)));
+
+    marquee.colouring = Colouring::Characters;
+    let frame = scrolled(&mut marquee, 0);
+    let (first, second) = (frame[(4, 7)], frame[(4, 12)]);
+    assert_ne!(first, RGB8::default());
+    assert_ne!(second, RGB8::default());
+    assert_ne!(first, second);
+}
+
+#[test]
+fn long_messages_are_cut_off() {
+    let mut marquee = Marquee::new();
+    let long = "é".repeat(MAX_MESSAGE);
+    marquee.set_message(&long);
+    assert_eq!(marquee.message(), &long[..MAX_MESSAGE]);
+}
@@ -0,0 +1,155 @@
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::MatrixDisplayer,
+    notification::{Icon, Notification, Overlay, Style, MAX_PENDING},
+    rgb8::RGB8,
+    single::Single,
+};
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+
+fn border(seconds: u32, priority: u8) -> Notification {
+    Notification {
+        style: Style::Border,
+        colour: RED,
+        duration_ms: seconds * 1000,
+        priority,
+    }
+}
+
+#[test]
+fn notifications_round_trip() {
+    for s in [
+        "border,5,1,ff0000",
+        "icon,5,2,00ff00,tick",
+        "text,10,3,ffffff,build failed, again",
+    ] {
+        let notification: Notification = s.parse().unwrap();
+        assert_eq!(notification.to_string(), s);
+    }
+    let icon: Notification = "icon,2,0,#0000ff,heart".parse().unwrap();
+    assert_eq!(icon.style, Style::Icon(Icon::Heart));
+    assert_eq!(icon.duration_ms, 2000);
+    assert_eq!(icon.colour, RGB8::new(0, 0, 255));
+
+    for bad in [
+        "border,5,1",
+        "border,5,1,ff0000,extra",
+        "icon,5,1,ff0000",
+        "icon,5,1,ff0000,smiley",
+        "text,5,1,ff0000",
+        "flash,5,1,ff0000",
+        "border,x,1,ff0000",
+        "border,5,256,ff0000",
+        "border,5,1,red",
+    ] {
+        assert!(bad.parse::<Notification>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn the_queue_is_in_priority_order() {
+    let mut overlay = Overlay::new();
+    for (seconds, priority) in [(1, 1), (2, 3), (3, 1), (4, 2)] {
+        overlay.push(border(seconds, priority)).unwrap();
+    }
+    let mut frame = Frame::<4, 4>::new();
+    let mut order = Vec::new();
+    while overlay.is_active() {
+        overlay.draw(&mut frame, 500);
+        if let Some(showing) = overlay.showing() {
+            order.push(showing.duration_ms / 1000);
+        }
+    }
+    order.dedup();
+    // most important first, and in the order they came within a priority
+    assert_eq!(order, [2, 4, 1, 3]);
+}
+
+#[test]
+fn a_full_queue_drops_the_least_important() {
+    let mut overlay = Overlay::new();
+    for _ in 0..MAX_PENDING {
+        overlay.push(border(1, 2)).unwrap();
+    }
+    assert!(overlay.push(border(1, 2)).is_err());
+    assert!(overlay.push(border(1, 3)).is_ok());
+    assert_eq!(overlay.pending(), MAX_PENDING);
+    overlay.clear();
+    assert!(!overlay.is_active());
+}
+
+#[test]
+fn more_important_notifications_interrupt() {
+    let mut overlay = Overlay::new();
+    let mut frame = Frame::<4, 4>::new();
+    overlay.push(border(3, 1)).unwrap();
+    overlay.draw(&mut frame, 1000);
+    overlay.push(border(5, 2)).unwrap();
+    overlay.draw(&mut frame, 1000);
+    assert_eq!(overlay.showing().unwrap().priority, 2);
+    // the first comes back afterwards for the two seconds it had left
+    assert_eq!(overlay.pending(), 1);
+    for _ in 0..5 {
+        overlay.draw(&mut frame, 1000);
+    }
+    assert_eq!(overlay.showing(), Some(&border(2, 1)));
+}
+
+#[test]
+fn the_border_flashes() {
+    let mut overlay = Overlay::new();
+    overlay.push(border(1, 0)).unwrap();
+    let mut frame = Frame::<4, 4>::new();
+    overlay.draw(&mut frame, 250);
+    assert_eq!(frame[(0, 0)], RED);
+    assert_eq!(frame[(3, 2)], RED);
+    assert_eq!(frame[(1, 1)], RGB8::default());
+
+    let mut frame = Frame::<4, 4>::new();
+    overlay.draw(&mut frame, 250);
+    assert_eq!(frame[(0, 0)], RGB8::default());
+}
+
+#[test]
+fn icons_and_text_dim_the_display() {
+    for s in ["icon,1,0,ff0000,cross", "text,1,0,ff0000,Hi"] {
+        let mut overlay = Overlay::new();
+        overlay.push(s.parse().unwrap()).unwrap();
+        let mut frame = Frame::<16, 16>::new();
+        // far enough for the text to have come on
+        for _ in 0..2 {
+            frame.fill(RGB8::new(200, 200, 200));
+            overlay.draw(&mut frame, 250);
+        }
+        assert!(frame.pixels().any(|(_, p)| p == RED), "{s}");
+        assert!(
+            frame.pixels().any(|(_, p)| p == RGB8::new(50, 50, 50)),
+            "{s}"
+        );
+        assert!(
+            frame.pixels().all(|(_, p)| p != RGB8::new(200, 200, 200)),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn the_display_carries_on_underneath() {
+    let (mut with, mut without) = (Single::new(), Single::new());
+    let mut overlay = Overlay::new();
+    overlay
+        .push("text,1,0,ffffff,Hello".parse().unwrap())
+        .unwrap();
+    let (mut a, mut b) = (Frame::<16, 16>::new(), Frame::<16, 16>::new());
+    for _ in 0..30 {
+        with.update(&mut a);
+        let mut shown = a;
+        overlay.draw(&mut shown, 50);
+        without.update(&mut b);
+        assert_eq!(a.0, b.0);
+    }
+    assert!(!overlay.is_active());
+    assert_eq!(a.0, b.0);
+    assert_eq!(with.position, without.position);
+}
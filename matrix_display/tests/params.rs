@@ -0,0 +1,130 @@
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::{Displays, EFFECTS},
+    params::{ParamError, ParamKind, ParamSpec, ParamValue},
+    rgb8::RGB8,
+};
+
+const LEVEL: ParamSpec = ParamSpec {
+    name: "level",
+    kind: ParamKind::Int { min: 1, max: 10 },
+    default: ParamValue::Int(5),
+};
+
+const MODE: ParamSpec = ParamSpec {
+    name: "mode",
+    kind: ParamKind::Enum(&["slow", "fast"]),
+    default: ParamValue::Enum(0),
+};
+
+fn effect(name: &str) -> Displays {
+    let info = EFFECTS.iter().find(|e| e.name == name).unwrap();
+    Displays::with_seed(info.id, 1).unwrap()
+}
+
+#[test]
+fn parse_and_show() {
+    assert_eq!(LEVEL.parse("7"), Ok(ParamValue::Int(7)));
+    assert_eq!(LEVEL.parse("11"), Err(ParamError::Invalid));
+    assert_eq!(LEVEL.parse("seven"), Err(ParamError::Invalid));
+    assert_eq!(MODE.parse("fast"), Ok(ParamValue::Enum(1)));
+    assert_eq!(MODE.parse("medium"), Err(ParamError::Invalid));
+    assert_eq!(MODE.show(ParamValue::Enum(1)).to_string(), "fast");
+
+    let colour = ParamSpec {
+        name: "colour",
+        kind: ParamKind::Colour,
+        default: ParamValue::Colour(RGB8::new(0, 0, 0)),
+    };
+    assert_eq!(
+        colour.parse("ff8000"),
+        Ok(ParamValue::Colour(RGB8::new(255, 128, 0)))
+    );
+    assert_eq!(colour.parse("#ff8000"), colour.parse("ff8000"));
+    assert!(colour.parse("ff80").is_err());
+    assert_eq!(
+        colour
+            .show(ParamValue::Colour(RGB8::new(1, 2, 255)))
+            .to_string(),
+        "0102ff"
+    );
+
+    let flag = ParamSpec {
+        name: "flag",
+        kind: ParamKind::Bool,
+        default: ParamValue::Bool(false),
+    };
+    assert_eq!(flag.parse("true"), Ok(ParamValue::Bool(true)));
+    assert!(!flag.accepts(ParamValue::Int(1)));
+}
+
+#[test]
+fn effects_start_at_their_defaults() {
+    for info in EFFECTS {
+        let display = Displays::with_seed(info.id, 1).unwrap();
+        let defaults: Vec<_> = info.params.iter().map(|p| p.default).collect();
+        assert_eq!(display.params().as_slice(), defaults, "{}", info.name);
+        for spec in info.params {
+            assert!(spec.accepts(spec.default), "{} {}", info.name, spec.name);
+            assert_eq!(
+                spec.parse(&spec.show(spec.default).to_string()),
+                Ok(spec.default)
+            );
+        }
+    }
+}
+
+#[test]
+fn parameters_change_while_running() {
+    let mut metaballs = effect("Metaballs");
+    let (count, spec) = metaballs.info().param("count").unwrap();
+    assert_eq!(metaballs.set_param(count, spec.parse("1").unwrap()), Ok(()));
+    assert_eq!(metaballs.param(count), Some(ParamValue::Int(1)));
+    assert_eq!(
+        metaballs.set_param(count, ParamValue::Int(0)),
+        Err(ParamError::Invalid)
+    );
+    assert_eq!(
+        metaballs.set_param(count, ParamValue::Bool(true)),
+        Err(ParamError::Invalid)
+    );
+    assert_eq!(
+        metaballs.set_param(99, ParamValue::Int(1)),
+        Err(ParamError::Unknown)
+    );
+    metaballs.update(&mut Frame::<16, 16>::new());
+}
+
+#[test]
+fn colours_are_drawn() {
+    let red = RGB8::new(255, 0, 0);
+    let mut cake = effect("Cake");
+    let (spawn, _) = cake.info().param("spawn").unwrap();
+    let (flame, _) = cake.info().param("flame").unwrap();
+    cake.set_param(spawn, ParamValue::Float(1.0)).unwrap();
+    cake.set_param(flame, ParamValue::Colour(red)).unwrap();
+    let mut frame = Frame::<16, 16>::new();
+    cake.update(&mut frame);
+    assert!(frame.pixels().any(|(_, p)| p == red));
+}
+
+#[test]
+fn long_lived_sparks_stay_on_the_buffer() {
+    fn run<const ROWS: usize, const COLS: usize>() {
+        let mut cake = effect("Cake");
+        let (spawn, _) = cake.info().param("spawn").unwrap();
+        let (life, spec) = cake.info().param("life").unwrap();
+        let ParamKind::Int { max, .. } = spec.kind else {
+            panic!("life isn't an int");
+        };
+        cake.set_param(spawn, ParamValue::Float(1.0)).unwrap();
+        cake.set_param(life, ParamValue::Int(max)).unwrap();
+        let mut frame = Frame::<ROWS, COLS>::new();
+        for _ in 0..500 {
+            cake.update(&mut frame);
+        }
+    }
+    run::<16, 16>();
+    run::<8, 8>();
+    run::<2, 2>();
+}
@@ -0,0 +1,108 @@
+use matrix_display::{
+    pio_emulator::{Level, PioEmulator},
+    pio_program,
+    pixel_format::PixelFormat,
+    rgb8::RGB8,
+    timing::{LedTiming, PioTiming},
+};
+
+const CLOCK_HZ: u32 = 125_000_000;
+
+/// The length of each bit's high and low parts in cycles. The low part of the last bit runs on
+/// into the reset, so it is left out.
+fn bits(levels: &[Level]) -> Vec<(u32, u32)> {
+    // skip the line being low before the first bit
+    levels[1..]
+        .chunks(2)
+        .map(|pair| {
+            assert!(pair[0].high, "bits start high");
+            (pair[0].cycles, pair.get(1).map_or(0, |low| low.cycles))
+        })
+        .collect()
+}
+
+fn waveform(timing: &PioTiming, format: PixelFormat, colours: &[RGB8]) -> Vec<Level> {
+    let program = pio_program::ws2812(timing);
+    let words = colours.iter().map(|&c| format.encode(format.convert(c)));
+    PioEmulator::new(&program, format.bits(), words).collect()
+}
+
+/// Read the waveform back into bytes, checking each bit is a valid 0 or 1
+fn decode(timing: &PioTiming, levels: &[Level]) -> Vec<u8> {
+    let (t1, t2, t3) = (timing.t1 as u32, timing.t2 as u32, timing.t3 as u32);
+    let bits = bits(levels);
+    let last = bits.len() - 1;
+    let bits: Vec<bool> = bits
+        .into_iter()
+        .enumerate()
+        .map(|(i, (high, low))| {
+            let one = match high {
+                h if h == t1 + t2 => true,
+                h if h == t1 => false,
+                h => panic!("bit {i} is high for {h} cycles"),
+            };
+            if i != last {
+                let expected = if one { t3 } else { t2 + t3 };
+                assert_eq!(low, expected, "bit {i} is low for {low} cycles");
+            }
+            one
+        })
+        .collect();
+    bits.chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &b| acc << 1 | b as u8))
+        .collect()
+}
+
+#[test]
+fn ws2812b_sends_grb_with_the_right_timing() {
+    let timing = LedTiming::WS2812B.pio_timing(CLOCK_HZ).unwrap();
+    let colours = [RGB8::new(0x12, 0x34, 0x56), RGB8::new(0xff, 0x00, 0x81)];
+    let levels = waveform(&timing, PixelFormat::DEFAULT, &colours);
+
+    // set pindirs and the first out keep the line low before the first bit
+    assert_eq!(
+        levels[0],
+        Level {
+            high: false,
+            cycles: 1 + timing.t3 as u32
+        }
+    );
+    assert_eq!(
+        decode(&timing, &levels),
+        [0x34, 0x12, 0x56, 0x00, 0xff, 0x81]
+    );
+}
+
+#[test]
+fn bit_lengths_match_the_datasheet() {
+    let led = LedTiming::WS2812B;
+    let timing = led.pio_timing(CLOCK_HZ).unwrap();
+    let ns = |cycles: u32| cycles * timing.divider * 1000 / 256 / (CLOCK_HZ / 1_000_000);
+    let levels = waveform(&timing, PixelFormat::DEFAULT, &[RGB8::new(0xf0, 0, 0)]);
+
+    // green is 0, red is 1111_0000: the first bit of red is the 9th bit
+    let bits = bits(&levels);
+    let (zero_high, zero_low) = bits[0];
+    let (one_high, one_low) = bits[8];
+    assert_eq!(ns(zero_high), led.t0h_ns as u32);
+    assert_eq!(ns(one_high), led.t1h_ns as u32);
+    assert_eq!(ns(zero_high + zero_low), led.bit_ns as u32);
+    assert_eq!(ns(one_high + one_low), led.bit_ns as u32);
+}
+
+#[test]
+fn other_orders_and_rgbw() {
+    let timing = LedTiming::SK6812.pio_timing(CLOCK_HZ).unwrap();
+    let colour = RGB8::with_white(0x11, 0x22, 0x33, 0x44);
+
+    let rgb: PixelFormat = "rgb".parse().unwrap();
+    let levels = waveform(&timing, rgb, &[colour]);
+    assert_eq!(decode(&timing, &levels), [0x55, 0x66, 0x77]);
+
+    let grbw: PixelFormat = "grbw".parse().unwrap();
+    let levels = waveform(&timing, grbw, &[colour, RGB8::new(1, 2, 3)]);
+    assert_eq!(
+        decode(&timing, &levels),
+        [0x11, 0x00, 0x22, 0x55, 0x01, 0x00, 0x02, 0x01]
+    );
+}
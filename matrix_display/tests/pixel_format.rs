@@ -0,0 +1,63 @@
+use matrix_display::{
+    pixel_format::{ColourOrder, PixelFormat},
+    rgb8::RGB8,
+};
+
+#[test]
+fn grb_matches_ws2812b_wire_order() {
+    let format = PixelFormat::DEFAULT;
+    assert_eq!(format.bits(), 24);
+    assert_eq!(format.encode(RGB8::new(0x11, 0x22, 0x33)), 0x2211_3300);
+}
+
+#[test]
+fn every_order_puts_channels_in_place() {
+    let colour = RGB8::new(0x11, 0x22, 0x33);
+    let cases = [
+        ("rgb", 0x1122_3300),
+        ("rbg", 0x1133_2200),
+        ("grb", 0x2211_3300),
+        ("gbr", 0x2233_1100),
+        ("brg", 0x3311_2200),
+        ("bgr", 0x3322_1100),
+    ];
+    for (name, word) in cases {
+        let format: PixelFormat = name.parse().unwrap();
+        assert_eq!(format.encode(colour), word, "{name}");
+        assert_eq!(format.to_string(), name);
+    }
+}
+
+#[test]
+fn rgbw_extracts_white() {
+    let format: PixelFormat = "grbw".parse().unwrap();
+    assert_eq!(
+        format,
+        PixelFormat {
+            order: ColourOrder::Grb,
+            rgbw: true,
+        }
+    );
+    assert_eq!(format.bits(), 32);
+    let converted = format.convert(RGB8::new(200, 150, 100));
+    assert_eq!(converted, RGB8::with_white(100, 50, 0, 100));
+    assert_eq!(format.encode(converted), 0x3264_0064);
+}
+
+#[test]
+fn rgb_mixes_white_into_the_colours() {
+    let format = PixelFormat::DEFAULT;
+    assert_eq!(
+        format.convert(RGB8::with_white(10, 250, 0, 20)),
+        RGB8::new(30, 255, 20)
+    );
+}
+
+#[test]
+fn bytes_round_trip() {
+    for name in ["rgb", "bgrw", "gbr"] {
+        let format: PixelFormat = name.parse().unwrap();
+        assert_eq!(PixelFormat::from_bytes(&format.to_bytes()), Some(format));
+    }
+    assert!("rgbx".parse::<PixelFormat>().is_err());
+}
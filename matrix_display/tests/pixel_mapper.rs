@@ -0,0 +1,157 @@
+use matrix_display::pixel_mapper::{Corner, PixelMapper, Rotation, Wiring};
+
+fn indices<const ROWS: usize, const COLS: usize>(mapper: &PixelMapper) -> Vec<usize> {
+    (0..ROWS)
+        .flat_map(|r| (0..COLS).map(move |c| (r, c)))
+        .map(|(r, c)| mapper.index::<ROWS, COLS>(r, c))
+        .collect()
+}
+
+#[test]
+fn default_matches_original_serpentine() {
+    // even rows run right to left, odd rows left to right
+    let mapper = PixelMapper::DEFAULT;
+    for row in 0..16 {
+        for col in 0..16 {
+            let expected = if row & 1 == 0 {
+                row * 16 + 15 - col
+            } else {
+                row * 16 + col
+            };
+            assert_eq!(mapper.index::<16, 16>(row, col), expected, "({row}, {col})");
+        }
+    }
+}
+
+#[test]
+fn progressive_from_top_left_is_row_major() {
+    let mapper = PixelMapper {
+        wiring: Wiring::Progressive,
+        start: Corner::TopLeft,
+        ..PixelMapper::DEFAULT
+    };
+    assert_eq!(indices::<4, 6>(&mapper), (0..24).collect::<Vec<_>>());
+}
+
+#[test]
+fn rotation_moves_top_left_corner() {
+    let mapper = PixelMapper {
+        wiring: Wiring::Progressive,
+        start: Corner::TopLeft,
+        ..PixelMapper::DEFAULT
+    };
+    let corner = |rotation| PixelMapper { rotation, ..mapper }.index::<4, 4>(0, 0);
+    assert_eq!(corner(Rotation::R0), 0);
+    assert_eq!(corner(Rotation::R90), 3);
+    assert_eq!(corner(Rotation::R180), 15);
+    assert_eq!(corner(Rotation::R270), 12);
+}
+
+#[test]
+fn tiles_are_chained_panel_by_panel() {
+    let mapper = PixelMapper {
+        wiring: Wiring::Progressive,
+        start: Corner::TopLeft,
+        tiles_down: 2,
+        tiles_across: 2,
+        ..PixelMapper::DEFAULT
+    };
+    // the whole first panel comes before any of the second
+    assert_eq!(mapper.index::<4, 4>(0, 0), 0);
+    assert_eq!(mapper.index::<4, 4>(1, 1), 3);
+    assert_eq!(mapper.index::<4, 4>(0, 2), 4);
+    assert_eq!(mapper.index::<4, 4>(2, 0), 8);
+    assert_eq!(mapper.index::<4, 4>(3, 3), 15);
+
+    let serpentine_tiles = PixelMapper {
+        tile_wiring: Wiring::Serpentine,
+        ..mapper
+    };
+    assert_eq!(serpentine_tiles.index::<4, 4>(2, 2), 8);
+}
+
+#[test]
+fn every_mapping_is_a_permutation() {
+    let wirings = [Wiring::Progressive, Wiring::Serpentine];
+    let corners = [
+        Corner::TopLeft,
+        Corner::TopRight,
+        Corner::BottomLeft,
+        Corner::BottomRight,
+    ];
+    let rotations = [Rotation::R0, Rotation::R90, Rotation::R180, Rotation::R270];
+    for wiring in wirings {
+        for start in corners {
+            for rotation in rotations {
+                for (mirror_x, mirror_y) in [(false, false), (true, false), (false, true)] {
+                    for (tiles_down, tiles_across) in [(1, 1), (2, 2), (1, 4)] {
+                        for tile_wiring in wirings {
+                            let mapper = PixelMapper {
+                                wiring,
+                                start,
+                                rotation,
+                                mirror_x,
+                                mirror_y,
+                                tiles_down,
+                                tiles_across,
+                                tile_wiring,
+                            };
+                            assert!(mapper.fits(8, 8));
+                            let mut seen = indices::<8, 8>(&mapper);
+                            seen.sort();
+                            assert_eq!(seen, (0..64).collect::<Vec<_>>(), "{mapper}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn fits_checks_tiles_and_rotation() {
+    let rotated = PixelMapper {
+        rotation: Rotation::R90,
+        ..PixelMapper::DEFAULT
+    };
+    assert!(rotated.fits(16, 16));
+    assert!(!rotated.fits(8, 16));
+    let tiled = PixelMapper {
+        tiles_across: 3,
+        ..PixelMapper::DEFAULT
+    };
+    assert!(!tiled.fits(16, 16));
+    assert!(tiled.fits(16, 48));
+}
+
+#[test]
+fn parses_and_prints() {
+    let mapper: PixelMapper = "progressive,bottom-left,270,xy,2x1".parse().unwrap();
+    assert_eq!(
+        mapper,
+        PixelMapper {
+            wiring: Wiring::Progressive,
+            start: Corner::BottomLeft,
+            rotation: Rotation::R270,
+            mirror_x: true,
+            mirror_y: true,
+            tiles_down: 2,
+            tiles_across: 1,
+            tile_wiring: Wiring::Progressive,
+        }
+    );
+    assert_eq!(mapper.to_string().parse(), Ok(mapper));
+    assert_eq!(
+        PixelMapper::DEFAULT.to_string(),
+        "serpentine,top-right,0,none,1x1,progressive"
+    );
+    assert!("serpentine,top-right,45,none,1x1".parse::<PixelMapper>().is_err());
+    assert!("serpentine,top-right".parse::<PixelMapper>().is_err());
+}
+
+#[test]
+fn bytes_round_trip() {
+    let mapper: PixelMapper = "serpentine,bottom-right,90,y,2x2,serpentine".parse().unwrap();
+    assert_eq!(PixelMapper::from_bytes(&mapper.to_bytes()), Some(mapper));
+    assert_eq!(PixelMapper::from_bytes(&[9; 8]), None);
+}
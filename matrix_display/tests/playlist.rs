@@ -0,0 +1,144 @@
+use matrix_display::{
+    matrix_displayer::EFFECTS,
+    params::ParamValue,
+    playlist::{Entry, Playlist, PlaylistPlayer, MAX_ENTRIES},
+};
+
+fn playing(spec: &str) -> Playlist {
+    Playlist {
+        enabled: true,
+        ..spec.parse().unwrap()
+    }
+}
+
+fn ids(player: &mut PlaylistPlayer, steps: usize) -> Vec<u8> {
+    (0..steps)
+        .map(|_| player.next_entry().unwrap().id)
+        .collect()
+}
+
+#[test]
+fn parse_and_display() {
+    let playlist: Playlist = "shuffle,0:30,1:60:1234".parse().unwrap();
+    assert!(playlist.shuffle);
+    assert_eq!(
+        playlist.entries,
+        [
+            Entry {
+                id: 0,
+                dwell_s: 30,
+                seed: None,
+                params: Default::default(),
+            },
+            Entry {
+                id: 1,
+                dwell_s: 60,
+                seed: Some(1234),
+                params: Default::default(),
+            },
+        ]
+    );
+    assert_eq!(playlist.to_string(), "shuffle,0:30,1:60:1234");
+    assert_eq!("2:5".parse::<Playlist>().unwrap().to_string(), "2:5");
+    assert!("0".parse::<Playlist>().is_err());
+    assert!("0:30:1:2".parse::<Playlist>().is_err());
+    assert!("200:30".parse::<Playlist>().is_err());
+    let unknown = format!("{}:30", EFFECTS.len());
+    assert!(unknown.parse::<Playlist>().is_err());
+
+    let full = vec!["0:1"; MAX_ENTRIES + 1].join(",");
+    assert!(full.parse::<Playlist>().is_err());
+}
+
+#[test]
+fn entries_can_set_params() {
+    let playlist: Playlist = "1:30:count=3;speed=0.005,2:10:99:spawn=0.5"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        playlist.to_string(),
+        "1:30:count=3;speed=0.005,2:10:99:spawn=0.5"
+    );
+    let metaballs = EFFECTS[1];
+    let (count, _) = metaballs.param("count").unwrap();
+    let params: Vec<_> = playlist.entries[0].params().collect();
+    assert_eq!(params[0], (count, ParamValue::Int(3)));
+    assert_eq!(params.len(), 2);
+    assert_eq!(playlist.entries[1].seed, Some(99));
+    assert_eq!(playlist.entries[1].params().count(), 1);
+
+    for bad in [
+        "1:30:count=99",
+        "1:30:colour=red",
+        "1:30:nothing=1",
+        "1:30:count",
+        "1:30:count=3:1",
+        "1:30:count=3;count=3;count=3;count=3;count=3;count=3;count=3",
+    ] {
+        assert!(bad.parse::<Playlist>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn bytes_round_trip() {
+    let playlist = playing("shuffle,0:30,1:60:1234,2:45");
+    assert_eq!(Playlist::from_bytes(&playlist.to_bytes()), Some(playlist));
+    assert_eq!(Playlist::from_bytes(&[0, 1, 30]), None);
+    assert_eq!(Playlist::from_bytes(&[0, EFFECTS.len() as u8, 30, 0]), None);
+}
+
+#[test]
+fn advances_after_the_dwell_time() {
+    let mut player = PlaylistPlayer::new(1);
+    assert_eq!(
+        player.set_playlist(playing("0:1,2:2")).map(|e| e.id),
+        Some(0)
+    );
+    assert_eq!(player.tick(999), None);
+    assert_eq!(player.tick(1).map(|e| e.id), Some(2));
+    assert_eq!(player.tick(1999), None);
+    assert_eq!(player.tick(1).map(|e| e.id), Some(0));
+
+    player.pause();
+    assert_eq!(player.tick(5000), None);
+    player.resume();
+    assert_eq!(player.tick(1000).map(|e| e.id), Some(2));
+}
+
+#[test]
+fn next_and_previous_wrap_around() {
+    let mut player = PlaylistPlayer::new(1);
+    player.set_playlist(playing("0:10,1:10,2:10"));
+    assert_eq!(ids(&mut player, 4), [1, 2, 0, 1]);
+    assert_eq!(player.previous_entry().map(|e| e.id), Some(0));
+    assert_eq!(player.previous_entry().map(|e| e.id), Some(2));
+}
+
+#[test]
+fn stopped_playlists_do_nothing() {
+    let mut player = PlaylistPlayer::new(1);
+    assert_eq!(player.set_playlist("0:1,1:1".parse().unwrap()), None);
+    assert!(!player.is_running());
+    assert_eq!(player.tick(10_000), None);
+    assert_eq!(player.next_entry(), None);
+    // setting the same playlist again doesn't restart it
+    player.set_playlist(playing("0:1,1:1"));
+    assert_eq!(player.set_playlist(playing("0:1,1:1")), None);
+}
+
+#[test]
+fn shuffle_plays_everything_each_time_round() {
+    let mut player = PlaylistPlayer::new(7);
+    let first = player
+        .set_playlist(playing("shuffle,0:1,1:1,2:1,3:1,4:1"))
+        .unwrap();
+    let mut played = vec![first.id];
+    played.extend(ids(&mut player, 19));
+    for round in played.chunks(5) {
+        let mut sorted = round.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, [0, 1, 2, 3, 4]);
+    }
+    assert!(played.windows(2).all(|w| w[0] != w[1]));
+    assert_ne!(played[..5], played[5..10]);
+}
@@ -0,0 +1,86 @@
+use matrix_display::{
+    power::{PowerBudget, PowerReport},
+    rgb8::RGB8,
+};
+
+const BUDGET: PowerBudget = PowerBudget {
+    budget_ma: 1000,
+    channel_ma: 20,
+    idle_ua: 1000,
+};
+
+#[test]
+fn estimates_full_white_and_idle() {
+    let white = [RGB8::new(255, 255, 255); 256];
+    assert_eq!(BUDGET.estimate_ma(&white), 256 * 60 + 256);
+    let black = [RGB8::default(); 256];
+    assert_eq!(BUDGET.estimate_ma(&black), 256);
+}
+
+#[test]
+fn frames_under_budget_are_untouched() {
+    let mut pixels = [RGB8::new(10, 0, 0); 256];
+    let report = BUDGET.limit(&mut pixels);
+    assert!(!report.limited);
+    assert_eq!(report.requested_ma, report.output_ma);
+    assert!(pixels.iter().all(|p| p.r == 10));
+}
+
+#[test]
+fn frames_over_budget_are_scaled_to_fit() {
+    let mut pixels = [RGB8::new(255, 255, 255); 256];
+    let report = BUDGET.limit(&mut pixels);
+    assert_eq!(
+        report,
+        PowerReport {
+            requested_ma: 15616,
+            output_ma: BUDGET.estimate_ma(&pixels),
+            limited: true,
+        }
+    );
+    assert!(report.output_ma <= 1000);
+    // the budget is used rather than wasted
+    assert!(report.output_ma > 900, "{report}");
+    assert!(pixels.iter().all(|p| p.r == p.g && p.g == p.b));
+}
+
+#[test]
+fn budgets_below_the_idle_draw() {
+    let budget = PowerBudget {
+        budget_ma: 100,
+        ..BUDGET
+    };
+    assert!(!budget.fits(256));
+    assert!(budget.fits(100));
+    assert!(BUDGET.fits(256));
+
+    // nothing is lit, so there's nothing to scale
+    let mut pixels = [RGB8::default(); 256];
+    let report = budget.limit(&mut pixels);
+    assert_eq!(
+        report,
+        PowerReport {
+            requested_ma: 256,
+            output_ma: 256,
+            limited: true,
+        }
+    );
+    assert!(pixels.iter().all(|&p| p == RGB8::default()));
+}
+
+#[test]
+fn parses_and_prints() {
+    let budget: PowerBudget = "2000,15,800".parse().unwrap();
+    assert_eq!(
+        budget,
+        PowerBudget {
+            budget_ma: 2000,
+            channel_ma: 15,
+            idle_ua: 800,
+        }
+    );
+    assert_eq!(budget.to_string().parse(), Ok(budget));
+    assert_eq!(PowerBudget::from_bytes(&budget.to_bytes()), Some(budget));
+    assert!("2000,300,800".parse::<PowerBudget>().is_err());
+    assert!("2000,15".parse::<PowerBudget>().is_err());
+}
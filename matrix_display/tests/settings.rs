@@ -0,0 +1,85 @@
+use matrix_display::{
+    matrix_displayer::EFFECTS,
+    pixel_mapper::PixelMapper,
+    playlist::{MAX_ENTRIES, MAX_PARAMS_LEN},
+    settings::{Settings, SETTINGS_LEN},
+};
+
+#[test]
+fn round_trip() {
+    let settings = Settings {
+        mapper: "progressive,top-left,180,x,1x2"
+            .parse::<PixelMapper>()
+            .unwrap(),
+        gamma: 18,
+        brightness: 200,
+        power: "500,18,600".parse().unwrap(),
+        format: "rgbw".parse().unwrap(),
+        timing: "300,900,1500,100".parse().unwrap(),
+        transition: "iris,1200".parse().unwrap(),
+        playlist: "shuffle,0:30,1:60:1234:count=3,2:45:life=9"
+            .parse()
+            .unwrap(),
+        zones: "7:0:0:8x16,1:8:0:8x16".parse().unwrap(),
+    };
+    assert_eq!(Settings::decode(&settings.encode()), settings);
+}
+
+#[test]
+fn the_largest_settings_fit() {
+    let mut playlist = String::from("shuffle");
+    for i in 0..MAX_ENTRIES {
+        playlist += &format!(",{}:3600:{}:", i % EFFECTS.len(), u64::MAX);
+        // the longest params each effect can take, as far as they fit
+        let info = &EFFECTS[i % EFFECTS.len()];
+        let mut params = String::new();
+        for spec in info.params.iter().cycle().take(20) {
+            let param = format!("{}={};", spec.name, spec.show(spec.default));
+            if params.len() + param.len() <= MAX_PARAMS_LEN {
+                params += &param;
+            }
+        }
+        playlist += &params;
+    }
+    let settings = Settings {
+        playlist: playlist.parse().unwrap(),
+        zones: "0:0:0:8x8,1:0:8:8x8,2:8:0:8x8,3:8:8:8x8".parse().unwrap(),
+        ..Settings::new()
+    };
+    assert_eq!(Settings::decode(&settings.encode()), settings);
+}
+
+#[test]
+fn settings_from_before_the_block_grew_still_load() {
+    let settings = Settings {
+        mapper: "progressive,top-left,180,x,1x2"
+            .parse::<PixelMapper>()
+            .unwrap(),
+        ..Settings::new()
+    };
+    let mut bytes = settings.encode();
+    // the old block was 256 bytes, checksummed at the end
+    bytes[255..].fill(0xff);
+    bytes[255] = bytes[..255]
+        .iter()
+        .fold(0u8, |acc, b| acc.rotate_left(1) ^ b);
+    assert_eq!(Settings::decode(&bytes), settings);
+}
+
+#[test]
+fn erased_flash_gives_defaults() {
+    assert_eq!(Settings::decode(&[0xff; SETTINGS_LEN]), Settings::new());
+}
+
+#[test]
+fn corruption_gives_defaults() {
+    let settings = Settings {
+        mapper: "progressive,top-left,180,x,1x2"
+            .parse::<PixelMapper>()
+            .unwrap(),
+        ..Settings::new()
+    };
+    let mut bytes = settings.encode();
+    bytes[6] ^= 1;
+    assert_eq!(Settings::decode(&bytes), Settings::new());
+}
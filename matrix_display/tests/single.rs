@@ -0,0 +1,59 @@
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::MatrixDisplayer,
+    rgb8::RGB8,
+    single::{Single, WalkOrder},
+};
+
+fn lit(frame: &Frame<3, 4>) -> Vec<(usize, usize)> {
+    frame
+        .pixels()
+        .filter(|(_, p)| *p != RGB8::default())
+        .map(|(at, _)| at)
+        .collect()
+}
+
+fn walk(order: WalkOrder, steps: usize) -> Vec<(usize, usize)> {
+    let mut single = Single {
+        order,
+        ..Single::new()
+    };
+    let mut frame = Frame::<3, 4>::new();
+    (0..steps)
+        .flat_map(|_| {
+            single.update(&mut frame);
+            lit(&frame)
+        })
+        .collect()
+}
+
+#[test]
+fn walks_in_order() {
+    assert_eq!(
+        walk(WalkOrder::Rows, 6),
+        [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (1, 1)]
+    );
+    assert_eq!(
+        walk(WalkOrder::Serpentine, 6),
+        [(0, 0), (0, 1), (0, 2), (0, 3), (1, 3), (1, 2)]
+    );
+    assert_eq!(
+        walk(WalkOrder::Columns, 5),
+        [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1)]
+    );
+}
+
+#[test]
+fn wraps_round_and_skips_ahead() {
+    let mut single = Single {
+        speed: 5,
+        ..Single::new()
+    };
+    let mut frame = Frame::<3, 4>::new();
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        single.update(&mut frame);
+        seen.extend(lit(&frame));
+    }
+    assert_eq!(seen, [(0, 0), (1, 1), (2, 2), (0, 3)]);
+}
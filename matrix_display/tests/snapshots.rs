@@ -0,0 +1,164 @@
+//! Golden-frame tests: every effect is stepped for a few frames from a fixed seed and the frame
+//! buffers are compared against the files in `tests/snapshots`.
+//!
+//! Run with `cargo test-host`. After an intended change to an effect, re-bless the snapshots with
+//! `BLESS=1 cargo test-host` and check the diff of the snapshot files looks right.
+
+use std::{fmt::Write, fs, path::PathBuf};
+
+use matrix_display::{
+    automaton::Automaton,
+    cake::Cake,
+    frame_buffer::{Frame, FrameBuffer},
+    game_of_life::GameOfLife,
+    marquee::{Colouring, Marquee},
+    matrix_displayer::MatrixDisplayer,
+    metaballs::Metaballs,
+    single::{Single, WalkOrder},
+    wheel::Wheel,
+    wrap::Wrap,
+};
+
+const ROWS: usize = 16;
+const COLS: usize = 16;
+const FRAMES: usize = 8;
+const SEED: u64 = 0x5eed;
+
+/// One line per row, one `rrggbb` hex triple per pixel
+fn format_frame(out: &mut String, frame: &Frame<ROWS, COLS>) {
+    for row in 0..ROWS {
+        let line = (0..COLS)
+            .map(|col| {
+                let p = frame[(row, col)];
+                format!("{:02x}{:02x}{:02x}", p.r, p.g, p.b)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(out, "{line}").unwrap();
+    }
+}
+
+/// Step a displayer built by `make` from `SEED` for `FRAMES` frames and compare against
+/// `tests/snapshots/{name}.txt`
+fn check_snapshot<D: MatrixDisplayer<ROWS, COLS>>(name: &str, make: impl FnOnce(u64) -> D) {
+    let mut displayer = make(SEED);
+
+    let mut frame = Frame::new();
+    let mut actual = String::new();
+    for i in 0..FRAMES {
+        displayer.update(&mut frame);
+        writeln!(actual, "# frame {i}").unwrap();
+        format_frame(&mut actual, &frame);
+    }
+
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", name]
+        .iter()
+        .collect::<PathBuf>()
+        .with_extension("txt");
+    if std::env::var_os("BLESS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}, run with BLESS=1 to create it",
+            path.display()
+        )
+    });
+    if let Some((line, (e, a))) = expected
+        .lines()
+        .zip(actual.lines())
+        .enumerate()
+        .find(|(_, (e, a))| e != a)
+    {
+        panic!(
+            "{name} differs from its snapshot at line {}\nexpected: {e}\n  actual: {a}\n\
+             run with BLESS=1 if the change is intended",
+            line + 1
+        );
+    }
+    assert_eq!(
+        expected.lines().count(),
+        actual.lines().count(),
+        "{name} snapshot has a different number of lines, run with BLESS=1 if the change is intended"
+    );
+}
+
+#[test]
+fn wheel() {
+    check_snapshot("wheel", |_| Wheel(0));
+}
+
+#[test]
+fn metaballs() {
+    check_snapshot("metaballs", Metaballs::<10>::new);
+}
+
+#[test]
+fn cake() {
+    check_snapshot("cake", Cake::<20>::new);
+}
+
+#[test]
+fn game_of_life() {
+    check_snapshot("game_of_life", GameOfLife::<ROWS, COLS>::new);
+}
+
+#[test]
+fn automaton() {
+    check_snapshot("automaton", Automaton::<ROWS, COLS>::new);
+}
+
+#[test]
+fn single() {
+    check_snapshot("single", |_| Single {
+        speed: 5,
+        order: WalkOrder::Serpentine,
+        ..Single::new()
+    });
+}
+
+#[test]
+fn wrap() {
+    check_snapshot("wrap", |_| Wrap {
+        phase: 250,
+        ..Wrap::new()
+    });
+}
+
+#[test]
+fn marquee() {
+    check_snapshot("marquee", |_| {
+        let mut marquee = Marquee::new();
+        marquee.speed = 2.0;
+        marquee.colouring = Colouring::Characters;
+        marquee
+    });
+}
+
+#[test]
+fn frame_buffer_fill_and_clear() {
+    let mut frame = Frame::<ROWS, COLS>::new();
+    frame.fill((1, 2, 3).into());
+    assert!(frame.pixels().all(|(_, p)| (p.r, p.g, p.b) == (1, 2, 3)));
+    frame.clear();
+    assert!(frame.pixels().all(|(_, p)| (p.r, p.g, p.b) == (0, 0, 0)));
+}
+
+#[test]
+fn same_seed_same_frames() {
+    let run = |seed| {
+        let mut displayer = Metaballs::<10>::new(seed);
+        let mut frame = Frame::<ROWS, COLS>::new();
+        let mut out = String::new();
+        for _ in 0..FRAMES {
+            displayer.update(&mut frame);
+            format_frame(&mut out, &frame);
+        }
+        out
+    };
+    assert_eq!(run(1), run(1));
+    assert_ne!(run(1), run(2));
+}
@@ -0,0 +1,63 @@
+use matrix_display::timing::{LedTiming, PioTiming};
+
+const CLOCK_HZ: u32 = 125_000_000;
+
+/// The length in ns of `cycles` PIO cycles
+fn ns(cycles: u8, divider: u32) -> u32 {
+    cycles as u32 * divider * 1000 / 256 / (CLOCK_HZ / 1_000_000)
+}
+
+#[test]
+fn ws2812b_keeps_the_original_program() {
+    assert_eq!(
+        LedTiming::WS2812B.pio_timing(CLOCK_HZ),
+        Some(PioTiming {
+            t1: 2,
+            t2: 5,
+            t3: 3,
+            divider: 15 * 256 + 160,
+        })
+    );
+}
+
+#[test]
+fn presets_are_close_to_their_timings() {
+    for timing in [LedTiming::WS2812B, LedTiming::WS2811, LedTiming::SK6812] {
+        let pio = timing.pio_timing(CLOCK_HZ).unwrap();
+        let t0h = ns(pio.t1, pio.divider);
+        let t1h = ns(pio.t1 + pio.t2, pio.divider);
+        let bit = ns(pio.t1 + pio.t2 + pio.t3, pio.divider);
+        assert!(t0h.abs_diff(timing.t0h_ns as u32) < 50, "{timing}: {pio:?}");
+        assert!(t1h.abs_diff(timing.t1h_ns as u32) < 50, "{timing}: {pio:?}");
+        assert!(bit.abs_diff(timing.bit_ns as u32) < 10, "{timing}: {pio:?}");
+    }
+}
+
+#[test]
+fn unreachable_timings_are_rejected() {
+    let too_slow = LedTiming {
+        t0h_ns: 100,
+        t1h_ns: 200,
+        bit_ns: 60000,
+        reset_us: 50,
+    };
+    assert_eq!(too_slow.pio_timing(CLOCK_HZ), None);
+    let too_fast = LedTiming {
+        t0h_ns: 5,
+        t1h_ns: 10,
+        bit_ns: 20,
+        reset_us: 50,
+    };
+    assert_eq!(too_fast.pio_timing(CLOCK_HZ), None);
+}
+
+#[test]
+fn parse_and_display() {
+    assert_eq!("sk6812".parse(), Ok(LedTiming::SK6812));
+    assert_eq!(LedTiming::WS2811.to_string(), "ws2811");
+    let custom: LedTiming = "300,900,1500,100".parse().unwrap();
+    assert_eq!(custom.to_string(), "300,900,1500,100");
+    assert_eq!(LedTiming::from_bytes(&custom.to_bytes()), Some(custom));
+    assert!("900,300,1500,100".parse::<LedTiming>().is_err());
+    assert!("300,900,1500".parse::<LedTiming>().is_err());
+}
@@ -0,0 +1,178 @@
+use matrix_display::{
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::Displays,
+    rgb8::RGB8,
+    transition::{Switcher, Transition, TransitionKind, PROGRESS_END},
+    wheel::Wheel,
+};
+
+const RED: RGB8 = RGB8::new(200, 0, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 100);
+
+fn frames() -> (Frame<6, 8>, Frame<6, 8>) {
+    let (mut from, mut to) = (Frame::new(), Frame::new());
+    from.fill(RED);
+    to.fill(BLUE);
+    (from, to)
+}
+
+fn blend(kind: TransitionKind, progress: u32) -> Frame<6, 8> {
+    let (from, to) = frames();
+    let mut out = Frame::new();
+    let transition = Transition {
+        kind,
+        duration_ms: 500,
+    };
+    transition.blend(progress, &from, &to, &mut out);
+    out
+}
+
+fn count(frame: &Frame<6, 8>, colour: RGB8) -> usize {
+    frame.pixels().filter(|(_, p)| *p == colour).count()
+}
+
+const KINDS: [TransitionKind; 7] = [
+    TransitionKind::Cut,
+    TransitionKind::Crossfade,
+    TransitionKind::WipeLeft,
+    TransitionKind::WipeRight,
+    TransitionKind::Dissolve,
+    TransitionKind::Slide,
+    TransitionKind::Iris,
+];
+
+#[test]
+fn every_kind_ends_on_the_new_display() {
+    for kind in KINDS {
+        assert_eq!(count(&blend(kind, PROGRESS_END), BLUE), 48, "{kind:?}");
+        if kind != TransitionKind::Cut {
+            assert_eq!(count(&blend(kind, 0), RED), 48, "{kind:?}");
+        }
+    }
+}
+
+#[test]
+fn crossfade_mixes_colours() {
+    let out = blend(TransitionKind::Crossfade, PROGRESS_END / 2);
+    assert_eq!(count(&out, RGB8::new(100, 0, 50)), 48);
+}
+
+#[test]
+fn wipes_and_slide_move_across() {
+    let out = blend(TransitionKind::WipeLeft, PROGRESS_END / 4);
+    assert_eq!(out[(0, 5)], RED);
+    assert_eq!(out[(0, 6)], BLUE);
+
+    let out = blend(TransitionKind::WipeRight, PROGRESS_END / 4);
+    assert_eq!(out[(0, 1)], BLUE);
+    assert_eq!(out[(0, 2)], RED);
+
+    // mark a column so its movement can be followed
+    let (mut from, mut to) = frames();
+    from[(0, 3)] = RGB8::new(1, 1, 1);
+    to[(0, 0)] = RGB8::new(2, 2, 2);
+    let mut out = Frame::new();
+    let slide = Transition {
+        kind: TransitionKind::Slide,
+        duration_ms: 500,
+    };
+    slide.blend(PROGRESS_END / 4, &from, &to, &mut out);
+    assert_eq!(out[(0, 1)], RGB8::new(1, 1, 1));
+    assert_eq!(out[(0, 6)], RGB8::new(2, 2, 2));
+}
+
+#[test]
+fn dissolve_and_iris_grow() {
+    for kind in [TransitionKind::Dissolve, TransitionKind::Iris] {
+        let mut shown = 0;
+        for progress in (0..=PROGRESS_END).step_by(16) {
+            let out = blend(kind, progress);
+            let now = count(&out, BLUE);
+            assert!(now >= shown, "{kind:?} shrank at {progress}");
+            shown = now;
+        }
+    }
+    // the iris opens from the middle
+    let out = blend(TransitionKind::Iris, PROGRESS_END / 4);
+    assert_eq!(out[(2, 3)], BLUE);
+    assert_eq!(out[(0, 0)], RED);
+}
+
+#[test]
+fn parse_and_display() {
+    assert_eq!(
+        "wipe-left,800".parse(),
+        Ok(Transition {
+            kind: TransitionKind::WipeLeft,
+            duration_ms: 800
+        })
+    );
+    assert_eq!("cut".parse(), Ok(Transition::CUT));
+    assert_eq!(
+        "iris".parse::<Transition>().unwrap().duration_ms,
+        Transition::DEFAULT.duration_ms
+    );
+    assert_eq!(Transition::DEFAULT.to_string(), "crossfade,500");
+    assert_eq!(Transition::CUT.to_string(), "cut");
+    assert!("spin,500".parse::<Transition>().is_err());
+    assert!("slide,fast".parse::<Transition>().is_err());
+    let slide: Transition = "slide,250".parse().unwrap();
+    assert_eq!(Transition::from_bytes(&slide.to_bytes()), Some(slide));
+}
+
+#[test]
+fn switcher_finishes_the_transition() {
+    let mut switcher = Switcher::<6, 8>::new(Displays::Wheel(Wheel(0)));
+    let mut frame = Frame::<6, 8>::new();
+    switcher.update(&mut frame, 0);
+
+    let transition = Transition {
+        kind: TransitionKind::Crossfade,
+        duration_ms: 100,
+    };
+    switcher.switch(Displays::Wheel(Wheel(128)), transition);
+    assert!(switcher.is_transitioning());
+    let spacing = switcher.frame_spacing();
+    let frames = 100 / spacing;
+    for _ in 0..frames {
+        switcher.update(&mut frame, spacing as u32);
+    }
+    assert!(!switcher.is_transitioning());
+
+    // from here on the new display draws on its own, matching one that never transitioned
+    let mut alone = Displays::Wheel(Wheel(128));
+    let mut expected = Frame::<6, 8>::new();
+    for _ in 0..=frames {
+        alone.update(&mut expected);
+    }
+    switcher.update(&mut frame, spacing as u32);
+    assert!(frame.pixels().eq(expected.pixels()));
+
+    switcher.switch(Displays::Wheel(Wheel(0)), Transition::CUT);
+    assert!(!switcher.is_transitioning());
+}
+
+#[test]
+fn transitions_last_as_long_as_asked_however_often_frames_come() {
+    let transition = Transition {
+        kind: TransitionKind::WipeLeft,
+        duration_ms: 100,
+    };
+    let mut switcher = Switcher::<6, 8>::new(Displays::Wheel(Wheel(0)));
+    let mut frame = Frame::<6, 8>::new();
+    switcher.switch(Displays::Wheel(Wheel(128)), transition);
+    // late frames
+    switcher.update(&mut frame, 60);
+    assert!(switcher.is_transitioning());
+    switcher.update(&mut frame, 60);
+    assert!(!switcher.is_transitioning());
+
+    // and early ones, from commands waking the matrix
+    switcher.switch(Displays::Wheel(Wheel(0)), transition);
+    for _ in 0..99 {
+        switcher.update(&mut frame, 1);
+    }
+    assert!(switcher.is_transitioning());
+    switcher.update(&mut frame, 1);
+    assert!(!switcher.is_transitioning());
+}
@@ -0,0 +1,145 @@
+use matrix_display::{
+    compositor::Compositor,
+    frame_buffer::{Frame, FrameBuffer},
+    matrix_displayer::Displays,
+    rgb8::RGB8,
+    single::Single,
+    transition::Switcher,
+    zones::Layout,
+};
+
+#[test]
+fn layouts_round_trip() {
+    for s in ["none", "7:0:0:8x16,1:8:0:8x16", "0:4:4:8x8", "1:2:3:5x13"] {
+        let layout: Layout = s.parse().unwrap();
+        assert_eq!(layout.to_string(), s);
+        assert_eq!(Layout::from_bytes(&layout.to_bytes()), Some(layout));
+    }
+    for bad in [
+        "",
+        "99:0:0:8x8",
+        "1:0:0:0x8",
+        "1:0:0:8x17",
+        "1:0:0:8",
+        "1:0:0:8x8:2",
+        "1:0:0:8x8,",
+        "1:0:0:8x8,1:0:8:8x8,1:8:0:8x8,1:8:8:8x8,1:0:0:8x8",
+    ] {
+        assert!(bad.parse::<Layout>().is_err(), "{bad}");
+    }
+    assert_eq!(Layout::from_bytes(&[1, 0, 0, 8]), None);
+    assert_eq!(Layout::from_bytes(&[1, 0, 0, 0, 8]), None);
+}
+
+#[test]
+fn layouts_have_to_fit_the_matrix() {
+    let fits = |s: &str| s.parse::<Layout>().unwrap().fits(16, 16);
+    assert!(fits("none"));
+    assert!(fits("0:0:0:16x16"));
+    assert!(fits("0:8:10:8x6"));
+    assert!(!fits("0:12:12:8x8"));
+    assert!(!fits("0:0:0:8x8,1:0:9:8x8"));
+
+    let mut compositor = Compositor::<16, 16>::new();
+    compositor
+        .set_layout(&"1:0:0:8x8".parse().unwrap(), 1)
+        .unwrap();
+    assert!(compositor
+        .set_layout(&"6:12:12:8x8".parse().unwrap(), 1)
+        .is_err());
+    assert_eq!(compositor.layout().to_string(), "1:0:0:8x8");
+}
+
+fn single() -> Switcher<16, 16> {
+    Switcher::new(Displays::Single(Single::new()))
+}
+
+#[test]
+fn zones_run_their_own_displays() {
+    let mut switcher = Switcher::new(Displays::try_from(0).unwrap());
+    let mut compositor = Compositor::new();
+    // a pixel walking round each half, over the wheel
+    compositor
+        .set_layout(&"5:0:0:8x16,5:8:0:8x16".parse().unwrap(), 1)
+        .unwrap();
+    let mut frame = Frame::<16, 16>::new();
+    for _ in 0..20 {
+        compositor.update(&mut switcher, &mut frame, 50);
+    }
+    let lit: Vec<_> = frame
+        .pixels()
+        .filter(|(_, p)| *p != RGB8::default())
+        .map(|(pos, _)| pos)
+        .collect();
+    // both have walked 20 pixels along the rows of their own half
+    assert_eq!(lit, [(1, 3), (9, 3)]);
+}
+
+#[test]
+fn zones_of_any_size_leave_the_rest_alone() {
+    let (mut with, mut without) = (single(), single());
+    let mut compositor = Compositor::new();
+    compositor
+        .set_layout(&"6:11:10:5x6".parse().unwrap(), 1)
+        .unwrap();
+    let (mut a, mut b) = (Frame::<16, 16>::new(), Frame::<16, 16>::new());
+    let mut wrap = Frame::<16, 16>::new();
+    let mut zone = Displays::try_from(6).unwrap();
+    for _ in 0..5 {
+        compositor.update(&mut with, &mut a, 50);
+        without.update(&mut b, 50);
+        zone.update(&mut wrap);
+    }
+    for ((row, col), p) in a.pixels() {
+        if (11..16).contains(&row) && (10..16).contains(&col) {
+            // the top left of the zone's display, drawn at full size
+            assert_eq!(p, wrap[(row - 11, col - 10)], "{row},{col}");
+        } else {
+            assert_eq!(p, b[(row, col)], "{row},{col}");
+        }
+    }
+}
+
+#[test]
+fn zones_keep_running_when_moved() {
+    let mut switcher = single();
+    let mut compositor = Compositor::new();
+    let mut frame = Frame::<16, 16>::new();
+    compositor
+        .set_layout(&"5:0:0:8x8".parse().unwrap(), 1)
+        .unwrap();
+    for _ in 0..3 {
+        compositor.update(&mut switcher, &mut frame, 50);
+    }
+    compositor
+        .set_layout(&"0:0:0:8x8,5:8:8:8x8".parse().unwrap(), 1)
+        .unwrap();
+    let zones = compositor.zones().zones();
+    match &zones[1].display {
+        Displays::Single(s) => assert_eq!(s.position, 3),
+        _ => unreachable!(),
+    }
+    // a different size starts again
+    compositor
+        .set_layout(&"5:0:8:16x8".parse().unwrap(), 1)
+        .unwrap();
+    match &compositor.zones().zones()[0].display {
+        Displays::Single(s) => assert_eq!(s.position, 0),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn zones_wake_the_matrix_at_their_own_pace() {
+    let mut switcher = single();
+    let mut compositor = Compositor::new();
+    let mut frame = Frame::<16, 16>::new();
+    // the wheel runs every 10ms, the single pixel every 50
+    compositor
+        .set_layout(&"0:0:0:8x8".parse().unwrap(), 1)
+        .unwrap();
+    compositor.update(&mut switcher, &mut frame, 0);
+    assert_eq!(compositor.frame_spacing(&switcher), 10);
+    compositor.set_layout(&Layout::new(), 1).unwrap();
+    assert_eq!(compositor.frame_spacing(&switcher), 50);
+}
@@ -1,6 +1,8 @@
 MEMORY {
     BOOT2 : ORIGIN = 0x10000000, LENGTH = 0x100
-    FLASH : ORIGIN = 0x10000100, LENGTH = 2048K - 0x100
+    FLASH : ORIGIN = 0x10000100, LENGTH = 2048K - 0x100 - 4K
+    /* The last sector of flash is kept free for the saved settings, see storage.rs */
+    SETTINGS : ORIGIN = 0x10000000 + 2048K - 4K, LENGTH = 4K
 
     /* Pick one of the two options for RAM layout     */
 
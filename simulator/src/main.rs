@@ -0,0 +1,272 @@
+//! Runs the matrix effects on the host, drawing each frame to the terminal.
+//!
+//! Usage: `simulator [display id] [--gain N] [--seed N] [--transition kind,ms] [--gamma N]
+//! [--brightness N] [--list]`. Frames go through the same gamma and brightness as on the device,
+//! given in tenths and out of 255 like `/gamma` and `/brightness`, and are then scaled up by the
+//! gain for the screen, which is far dimmer than the LEDs. The gain defaults to making full
+//! brightness white. Type a
+//! display id (optionally followed by a seed) and press enter while it is running to switch
+//! effects, the same way `/run/{id}` and `/run/{id}/{seed}` do on the device, using the given
+//! transition. A line of `name=value` changes a parameter of the running display instead, like
+//! `/param/{id}/{name}/{value}` (e.g. `rule=B36/S23` on the Automaton), `text=message` shows a
+//! message like `/text/{message}`, `layers=...` runs other displays over it like `/layers/...`,
+//! `zones=...` splits the matrix like `/zones/...` and `notify=...` shows a notification over it
+//! like `/notify/...`.
+//! `--list` prints the effects, their ids and their parameters.
+
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    mem,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use matrix_display::{
+    compositor::{Compositor, Stack},
+    correction::ColourCorrection,
+    frame_buffer::Frame,
+    marquee::Marquee,
+    matrix_displayer::{Displays, DEFAULT_EFFECT, EFFECTS},
+    notification::{Notification, Overlay},
+    rgb8::RGB8,
+    transition::{Switcher, Transition},
+    zones::Layout,
+};
+
+const ROWS: usize = 16;
+const COLS: usize = 16;
+
+/// Draw the frame using the upper half block, so each character cell shows two rows of pixels:
+/// the top one as the foreground colour and the bottom one as the background
+fn render(out: &mut impl Write, frame: &Frame<ROWS, COLS>, gain: f32) -> io::Result<()> {
+    let scale = |v: u8| (v as f32 * gain).min(255.0) as u8;
+    let colour = |p: RGB8| (scale(p.r), scale(p.g), scale(p.b));
+
+    write!(out, "\x1b[H")?;
+    for row in (0..ROWS).step_by(2) {
+        for col in 0..COLS {
+            let (tr, tg, tb) = colour(frame[(row, col)]);
+            let (br, bg, bb) = if row + 1 < ROWS {
+                colour(frame[(row + 1, col)])
+            } else {
+                (0, 0, 0)
+            };
+            write!(out, "\x1b[38;2;{tr};{tg};{tb}m\x1b[48;2;{br};{bg};{bb}m▀")?;
+        }
+        writeln!(out, "\x1b[0m")?;
+    }
+    out.flush()
+}
+
+/// Without hardware randomness, seed from the clock so each run looks different
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Something typed on stdin
+enum Change {
+    Display(Box<Displays>),
+    /// A parameter name and value, still to be checked against the running display
+    Param(String, String),
+    Text(String),
+    Layers(Stack),
+    Zones(Layout),
+    Notify(Notification),
+}
+
+fn set_param(display: &mut Displays, name: &str, value: &str) {
+    let Some((index, spec)) = display.info().param(name) else {
+        eprintln!("{} has no parameter {name:?}", display.info().name);
+        return;
+    };
+    match spec.parse(value) {
+        Ok(value) => display.set_param(index, value).unwrap(),
+        Err(_) => eprintln!("{value:?} isn't a valid {name}"),
+    }
+}
+
+fn main() {
+    let mut display_id = DEFAULT_EFFECT;
+    let mut gain = None;
+    let mut gamma = ColourCorrection::DEFAULT_GAMMA;
+    let mut brightness = ColourCorrection::DEFAULT_BRIGHTNESS;
+    let mut seed = None;
+    let mut transition = Transition::DEFAULT;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--gain" => {
+                gain = Some(
+                    args.next()
+                        .and_then(|g| g.parse().ok())
+                        .expect("--gain takes a number"),
+                )
+            }
+            "--gamma" => {
+                gamma = args
+                    .next()
+                    .and_then(|g| g.parse().ok())
+                    .expect("--gamma takes a number of tenths")
+            }
+            "--brightness" => {
+                brightness = args
+                    .next()
+                    .and_then(|b| b.parse().ok())
+                    .expect("--brightness takes a number up to 255")
+            }
+            "--seed" => {
+                seed = Some(
+                    args.next()
+                        .and_then(|s| s.parse().ok())
+                        .expect("--seed takes a number"),
+                )
+            }
+            "--transition" => {
+                transition = args
+                    .next()
+                    .and_then(|t| t.parse().ok())
+                    .expect("--transition takes a kind and duration, e.g. crossfade,500")
+            }
+            "--list" => {
+                for effect in EFFECTS {
+                    println!(
+                        "{:>3} {:<12} {}",
+                        effect.id, effect.name, effect.description
+                    );
+                    for spec in effect.params {
+                        println!("{:16}{}={}", "", spec.name, spec.show(spec.default));
+                    }
+                }
+                return;
+            }
+            id => display_id = id.parse().expect("display id should be a number"),
+        }
+    }
+
+    let correction = ColourCorrection::new(gamma, brightness);
+    let gain = gain.unwrap_or(255.0 / brightness.max(1) as f32);
+
+    let display = Displays::with_seed(display_id, seed.unwrap_or_else(time_seed))
+        .expect("no display with that id");
+    let mut switcher = Switcher::new(display);
+
+    // Stand-in for the firmware's command channel and the zones setting: ids typed on stdin
+    // switch the running display, and name=value lines change its parameters, message, layers or
+    // zones, or show a notification
+    let (signal, changes) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if let Some((name, value)) = line.trim().split_once('=') {
+                let change = match name {
+                    "text" => Change::Text(value.to_owned()),
+                    "layers" => match value.parse() {
+                        Ok(stack) => Change::Layers(stack),
+                        Err(()) => {
+                            eprintln!("{value:?} isn't a valid stack of layers");
+                            continue;
+                        }
+                    },
+                    "zones" => match value.parse() {
+                        Ok(layout) => Change::Zones(layout),
+                        Err(()) => {
+                            eprintln!("{value:?} isn't a valid layout of zones");
+                            continue;
+                        }
+                    },
+                    "notify" => match value.parse() {
+                        Ok(notification) => Change::Notify(notification),
+                        Err(()) => {
+                            eprintln!("{value:?} isn't a valid notification");
+                            continue;
+                        }
+                    },
+                    _ => Change::Param(name.to_owned(), value.to_owned()),
+                };
+                if signal.send(change).is_err() {
+                    break;
+                }
+                continue;
+            }
+            let mut words = line.split_whitespace().map(str::parse::<u64>);
+            let display = match (words.next(), words.next()) {
+                (Some(Ok(id)), None) => Displays::with_seed(id as usize, time_seed()),
+                (Some(Ok(id)), Some(Ok(seed))) => Displays::with_seed(id as usize, seed),
+                _ => None,
+            };
+            match display {
+                Some(d) => {
+                    if signal.send(Change::Display(Box::new(d))).is_err() {
+                        break;
+                    }
+                }
+                None => eprintln!("expected a display id and optional seed, got {line:?}"),
+            }
+        }
+    });
+
+    let mut compositor = Compositor::new();
+    let mut overlay = Overlay::new();
+    let mut frame = Frame::<ROWS, COLS>::new();
+    let mut last_frame = Instant::now();
+    // whether to move on a frame, rather than just show the last one again
+    let mut redraw = true;
+    let mut out = io::stdout().lock();
+    write!(out, "\x1b[2J").unwrap();
+    loop {
+        let mut elapsed_ms = 0;
+        if mem::take(&mut redraw) {
+            let now = Instant::now();
+            elapsed_ms = (now - last_frame).as_millis() as u32;
+            last_frame = now;
+            compositor.update(&mut switcher, &mut frame, elapsed_ms);
+        }
+        // like on the device, the notification goes over a copy of the frame, and then the copy
+        // is corrected for the LEDs
+        let mut shown = frame;
+        overlay.draw(&mut shown, elapsed_ms);
+        for row in shown.0.iter_mut() {
+            row.iter_mut().for_each(|p| *p = correction.apply(*p));
+        }
+        render(&mut out, &shown, gain).unwrap();
+        match switcher.current().seed() {
+            Some(seed) => writeln!(out, "seed {seed:<20}").unwrap(),
+            None => writeln!(out, "{:25}", "").unwrap(),
+        }
+        // changes are shown straight away, but don't move the effects on
+        let next_frame = last_frame + Duration::from_millis(compositor.frame_spacing(&switcher));
+        match changes.recv_timeout(next_frame.saturating_duration_since(Instant::now())) {
+            Ok(Change::Display(d)) => switcher.switch(*d, transition),
+            Ok(Change::Param(name, value)) => set_param(switcher.current_mut(), &name, &value),
+            Ok(Change::Text(message)) => {
+                if !switcher.current_mut().set_message(&message) {
+                    let mut marquee = Marquee::new();
+                    marquee.set_message(&message);
+                    switcher.switch(Displays::Marquee(marquee), transition);
+                }
+            }
+            Ok(Change::Layers(stack)) => compositor.set_stack(&stack, time_seed()),
+            Ok(Change::Zones(layout)) => {
+                if compositor.set_layout(&layout, time_seed()).is_err() {
+                    eprintln!("{layout} doesn't fit the matrix");
+                }
+            }
+            Ok(Change::Notify(notification)) => {
+                if overlay.push(notification).is_err() {
+                    eprintln!("too many notifications waiting");
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => redraw = true,
+            // stdin has closed, so just keep running the current display
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                thread::sleep(next_frame.saturating_duration_since(Instant::now()));
+                redraw = true;
+            }
+        }
+    }
+}
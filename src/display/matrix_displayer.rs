@@ -1,58 +1,237 @@
-use embassy_futures::select::select;
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
+    future::pending,
+    mem,
+};
+
+use embassy_futures::select::{select3, Either3};
 use embassy_rp::{
     peripherals::{DMA_CH1, PIN_16, PIO1},
     pio::Pio,
 };
 
-use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
-use embassy_time::Timer;
-use log::info;
+use embassy_sync::{
+    blocking_mutex::{raw::CriticalSectionRawMutex, Mutex},
+    channel::Channel,
+    mutex,
+    signal::Signal,
+};
+use embassy_time::{with_timeout, Duration, Instant, Timer};
+use heapless::String;
+use log::warn;
+use matrix_display::{
+    compositor::{Compositor, Stack},
+    marquee::{Marquee, MAX_MESSAGE},
+    matrix_displayer::{Displays, DEFAULT_EFFECT},
+    notification::{Notification, Overlay},
+    params::{ParamValue, ParamValues},
+    pixel_mapper::PixelMapper,
+    playlist::{Entry, PlaylistPlayer, PlaylistStatus},
+    power::PowerReport,
+    rng::random_seed,
+    settings::Settings,
+    transition::{Switcher, Transition},
+    zones::Layout,
+};
+
+use super::{ws2812::Ws2812, COLS, ROWS};
+
+/// The seed of the display that is currently running, so a run can be reproduced from the web page
+pub static CURRENT_SEED: Mutex<CriticalSectionRawMutex, Cell<Option<u64>>> =
+    Mutex::new(Cell::new(None));
+
+/// The estimated current draw of the last frame sent to the LEDs
+pub static POWER_REPORT: Mutex<CriticalSectionRawMutex, Cell<PowerReport>> =
+    Mutex::new(Cell::new(PowerReport {
+        requested_ma: 0,
+        output_ma: 0,
+        limited: false,
+    }));
+
+/// Ask the matrix task to step through the playlist
+#[derive(Debug, Copy, Clone)]
+pub enum PlaylistCommand {
+    Next,
+    Previous,
+    Pause,
+    Resume,
+}
+
+/// Where the playlist is up to, for the web page
+pub static PLAYLIST_STATUS: Mutex<CriticalSectionRawMutex, Cell<PlaylistStatus>> =
+    Mutex::new(Cell::new(PlaylistStatus {
+        running: false,
+        paused: false,
+        entry: 0,
+    }));
+
+/// The id and parameter values of the display that is currently running, for the web page
+pub static CURRENT_PARAMS: Mutex<CriticalSectionRawMutex, RefCell<(usize, ParamValues)>> =
+    Mutex::new(RefCell::new((DEFAULT_EFFECT, ParamValues::new())));
 
-use super::{cake::Cake, metaballs::Metaballs, wheel::Wheel, ws2812::Ws2812};
+/// The layers running over the display, for the web page
+pub static CURRENT_LAYERS: Mutex<CriticalSectionRawMutex, RefCell<Stack>> =
+    Mutex::new(RefCell::new(Stack::new()));
 
-pub trait MatrixDisplayer<const ROWS: usize, const COLS: usize> {
-    fn update(&mut self, ws2812: &mut Ws2812<'_, PIO1, 0, ROWS, COLS>);
-    fn iterate(&self) -> impl Iterator<Item = (usize, usize)> {
-        (0..ROWS).flat_map(|r| (0..COLS).map(move |c| (r, c)))
+/// Something for the matrix task to do, sent with [`send_command`]
+pub enum Command {
+    /// Switch to a display, with the given transition or else the default one
+    Run(Displays, Option<Transition>),
+    /// Change a parameter of the running display, given as the effect id, the parameter's index
+    /// and its new value
+    SetParam(usize, usize, ParamValue),
+    /// Show a message on the text marquee, switching to it first if something else is running
+    Text(String<MAX_MESSAGE>),
+    /// Change the brightness straight away. Saving it is up to the sender.
+    SetBrightness(u8),
+    /// Change how pixels map onto the LEDs straight away. Saving it is up to the sender.
+    SetMapper(PixelMapper),
+    /// Freeze the matrix on the frame it is showing, playlist and all
+    Pause,
+    Resume,
+    /// Move on one frame, pausing first if need be
+    Step,
+    Playlist(PlaylistCommand),
+    /// Run a stack of displays over the running one, blended onto it
+    Layers(Stack),
+    /// Show a notification over the running display
+    Notify(Notification),
+    ClearNotifications,
+}
+
+/// Why a command couldn't be applied
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The parameter belongs to an effect that has stopped running
+    NotRunning,
+    /// The value doesn't fit the parameter
+    InvalidValue,
+    /// The pixel mapping is bigger than the matrix
+    DoesNotFit,
+    /// Too many notifications are waiting
+    QueueFull,
+    /// The matrix task didn't answer in time
+    Timeout,
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NotRunning => "That effect isn't running",
+            Self::InvalidValue => "That isn't a valid value for the parameter",
+            Self::DoesNotFit => "That mapping doesn't fit the matrix",
+            Self::QueueFull => "Too many notifications are waiting",
+            Self::Timeout => "The matrix didn't answer in time",
+        })
     }
 }
 
-pub enum Displays {
-    Wheel(Wheel),
-    // Wrap(Wrap),
-    // Single(Single),
-    Metaballs(Metaballs<10>),
-    Cake(Cake<20>),
+struct Request {
+    /// Matches the reply to the request
+    seq: u32,
+    command: Command,
 }
 
-impl Displays {
-    pub fn frame_spacing(&self) -> u64 {
-        match self {
-            Displays::Wheel(_) => 10,
-            Displays::Metaballs(_) => 50,
-            Displays::Cake(_) => 50,
+static COMMAND_CHANNEL: Channel<CriticalSectionRawMutex, Request, 1> = Channel::new();
+static REPLY_SIGNAL: Signal<CriticalSectionRawMutex, (u32, Result<(), CommandError>)> =
+    Signal::new();
+/// Held while a command is in flight, so each sender gets its own reply. It holds the last
+/// sequence number used.
+static SENDER: mutex::Mutex<CriticalSectionRawMutex, u32> = mutex::Mutex::new(0);
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Send a command to the matrix task, waiting until it has been applied
+pub async fn send_command(command: Command) -> Result<(), CommandError> {
+    let mut last_seq = SENDER.lock().await;
+    let seq = last_seq.wrapping_add(1);
+    *last_seq = seq;
+    let reply = async {
+        COMMAND_CHANNEL.send(Request { seq, command }).await;
+        // a reply to an earlier command that timed out may still turn up
+        loop {
+            let (reply_seq, result) = REPLY_SIGNAL.wait().await;
+            if reply_seq == seq {
+                return result;
+            }
         }
+    };
+    with_timeout(REPLY_TIMEOUT, reply)
+        .await
+        .unwrap_or(Err(CommandError::Timeout))
+}
+
+fn publish(display: &Displays) {
+    CURRENT_SEED.lock(|s| s.set(display.seed()));
+    CURRENT_PARAMS.lock(|p| *p.borrow_mut() = (display.info().id, display.params()));
+}
+
+fn switch(switcher: &mut Switcher<ROWS, COLS>, display: Displays, transition: Transition) {
+    publish(&display);
+    switcher.switch(display, transition);
+}
+
+fn set_param(
+    switcher: &mut Switcher<ROWS, COLS>,
+    id: usize,
+    index: usize,
+    value: ParamValue,
+) -> Result<(), CommandError> {
+    let display = switcher.current_mut();
+    if display.info().id != id {
+        return Err(CommandError::NotRunning);
     }
+    display
+        .set_param(index, value)
+        .map_err(|_| CommandError::InvalidValue)?;
+    publish(display);
+    Ok(())
 }
 
-impl TryFrom<usize> for Displays {
-    type Error = ();
+fn show_text(switcher: &mut Switcher<ROWS, COLS>, message: &str, transition: Transition) {
+    if !switcher.current_mut().set_message(message) {
+        let mut marquee = Marquee::new();
+        marquee.set_message(message);
+        switch(switcher, Displays::Marquee(marquee), transition);
+    }
+}
 
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
-        Ok(match value {
-            0 => Self::Wheel(Wheel(0)),
-            1 => Self::Metaballs(Metaballs::new()),
-            2 => Self::Cake(Cake::new()),
-            _ => return Err(()),
-        })
+fn play(switcher: &mut Switcher<ROWS, COLS>, entry: Entry, transition: Transition) {
+    let seed = entry.seed.unwrap_or_else(random_seed);
+    let Some(mut display) = Displays::with_seed(entry.id as usize, seed) else {
+        warn!("Playlist entry {} isn't a display", entry.id);
+        return;
+    };
+    for (index, value) in entry.params() {
+        if display.set_param(index, value).is_err() {
+            warn!("Playlist entry {} can't set {}", entry.id, entry.params);
+        }
     }
+    switch(switcher, display, transition);
 }
 
-async fn change_on_signal(
-    state: &mut Displays,
-    signal: &'static Signal<CriticalSectionRawMutex, Displays>,
-) {
-    *state = signal.wait().await;
+fn apply_settings(ws2812: &mut Ws2812<'_, PIO1, 0, ROWS, COLS>, settings: &Settings) {
+    if ws2812.set_mapper(settings.mapper).is_err() {
+        warn!("Pixel mapping {} doesn't fit the display", settings.mapper);
+    }
+    ws2812.set_correction(settings.gamma, settings.brightness);
+    if ws2812.set_power_budget(settings.power).is_err() {
+        warn!("Power budget {} is below the idle draw", settings.power);
+    }
+    ws2812.set_format(settings.format);
+    if settings.timing != ws2812.timing() {
+        warn!(
+            "LED timing {} takes effect after a restart",
+            settings.timing
+        );
+    }
+}
+
+fn set_layout(compositor: &mut Compositor<ROWS, COLS>, zones: &Layout) {
+    if compositor.set_layout(zones, random_seed()).is_err() {
+        warn!("Zones {} don't fit the display", zones);
+    }
 }
 
 #[embassy_executor::task]
@@ -60,34 +239,149 @@ pub async fn matrix_task(
     mut pio: Pio<'static, PIO1>,
     dma: DMA_CH1,
     pin: PIN_16,
-    signal: &'static Signal<CriticalSectionRawMutex, Displays>,
+    settings: Settings,
+    settings_signal: &'static Signal<CriticalSectionRawMutex, Settings>,
 ) {
-    let mut ws2812: Ws2812<'_, embassy_rp::peripherals::PIO1, 0, 16, 16> =
-        Ws2812::new(&mut pio.common, pio.sm0, dma, pin);
-    let mut state = Displays::try_from(2).unwrap();
+    let mut ws2812: Ws2812<'_, embassy_rp::peripherals::PIO1, 0, ROWS, COLS> =
+        Ws2812::new(&mut pio.common, pio.sm0, dma, pin, settings.timing);
+    apply_settings(&mut ws2812, &settings);
+    let mut transition = settings.transition;
+    let mut gamma = settings.gamma;
+    let mut switcher = Switcher::new(Displays::try_from(DEFAULT_EFFECT).unwrap());
+    publish(switcher.current());
+    let mut player = PlaylistPlayer::new(random_seed());
+    if let Some(entry) = player.set_playlist(settings.playlist) {
+        play(&mut switcher, entry, Transition::CUT);
+    }
+    let mut compositor = Compositor::new();
+    set_layout(&mut compositor, &settings.zones);
+    let mut overlay = Overlay::new();
+    let mut last_frame = Instant::now();
+    let mut paused = false;
+    // whether to move on a frame, rather than just send the last one out again
+    let mut redraw = true;
     loop {
-        match state {
-            Displays::Wheel(ref mut w) => {
-                w.update(&mut ws2812);
+        let mut elapsed_ms = 0;
+        if mem::take(&mut redraw) {
+            // time the playlist and layers by the clock, so waits cut short by commands and the
+            // time spent drawing all count, but a step moves on one frame however long it has
+            // been
+            let now = Instant::now();
+            elapsed_ms = if paused {
+                switcher.frame_spacing() as u32
+            } else {
+                (now - last_frame).as_millis() as u32
+            };
+            last_frame = now;
+            if let Some(entry) = player.tick(elapsed_ms) {
+                play(&mut switcher, entry, transition);
             }
-            // Displays::Wrap(ref mut w) => {
-            //     w.update(&mut ws2812);
-            // }
-            // Displays::Single(ref mut w) => {
-            //     w.update(&mut ws2812);
-            // }
-            Displays::Metaballs(ref mut w) => {
-                w.update(&mut ws2812);
+            compositor.update(&mut switcher, &mut ws2812, elapsed_ms);
+        }
+        // this also shows changes to the brightness and other settings without moving the
+        // effects on. The notification goes over a copy, so the effects never draw on from it.
+        let report = ws2812.write(|frame| overlay.draw(frame, elapsed_ms)).await;
+        POWER_REPORT.lock(|r| r.set(report));
+        PLAYLIST_STATUS.lock(|s| s.set(player.status()));
+        let next_frame = last_frame + Duration::from_millis(compositor.frame_spacing(&switcher));
+        let next_frame = async {
+            if paused {
+                pending().await
+            } else {
+                Timer::at(next_frame).await
             }
-            Displays::Cake(ref mut w) => {
-                w.update(&mut ws2812);
+        };
+        match select3(
+            next_frame,
+            COMMAND_CHANNEL.receive(),
+            settings_signal.wait(),
+        )
+        .await
+        {
+            Either3::First(()) => redraw = true,
+            Either3::Second(Request { seq, command }) => {
+                let result = match command {
+                    Command::Run(display, t) => {
+                        switch(&mut switcher, display, t.unwrap_or(transition));
+                        redraw = true;
+                        Ok(())
+                    }
+                    Command::SetParam(id, index, value) => {
+                        set_param(&mut switcher, id, index, value)
+                    }
+                    Command::Text(message) => {
+                        show_text(&mut switcher, &message, transition);
+                        redraw = true;
+                        Ok(())
+                    }
+                    Command::SetBrightness(brightness) => {
+                        ws2812.set_correction(gamma, brightness);
+                        Ok(())
+                    }
+                    Command::SetMapper(mapper) => ws2812
+                        .set_mapper(mapper)
+                        .map_err(|()| CommandError::DoesNotFit),
+                    Command::Pause => {
+                        paused = true;
+                        Ok(())
+                    }
+                    Command::Resume => {
+                        paused = false;
+                        last_frame = Instant::now();
+                        Ok(())
+                    }
+                    Command::Step => {
+                        (paused, redraw) = (true, true);
+                        Ok(())
+                    }
+                    Command::Playlist(command) => {
+                        let entry = match command {
+                            PlaylistCommand::Next => player.next_entry(),
+                            PlaylistCommand::Previous => player.previous_entry(),
+                            PlaylistCommand::Pause => {
+                                player.pause();
+                                None
+                            }
+                            PlaylistCommand::Resume => {
+                                player.resume();
+                                None
+                            }
+                        };
+                        if let Some(entry) = entry {
+                            play(&mut switcher, entry, transition);
+                            redraw = true;
+                        }
+                        Ok(())
+                    }
+                    Command::Layers(stack) => {
+                        compositor.set_stack(&stack, random_seed());
+                        CURRENT_LAYERS.lock(|l| *l.borrow_mut() = stack);
+                        redraw = true;
+                        Ok(())
+                    }
+                    Command::Notify(notification) => overlay
+                        .push(notification)
+                        .map_err(|_| CommandError::QueueFull),
+                    Command::ClearNotifications => {
+                        overlay.clear();
+                        Ok(())
+                    }
+                };
+                REPLY_SIGNAL.signal((seq, result));
+            }
+            Either3::Third(settings) => {
+                apply_settings(&mut ws2812, &settings);
+                gamma = settings.gamma;
+                if settings.zones != compositor.layout() {
+                    set_layout(&mut compositor, &settings.zones);
+                    redraw = true;
+                }
+                transition = settings.transition;
+                if let Some(entry) = player.set_playlist(settings.playlist) {
+                    play(&mut switcher, entry, transition);
+                    redraw = true;
+                }
             }
         }
-        ws2812.write().await;
-        select(
-            Timer::after_millis(state.frame_spacing()),
-            change_on_signal(&mut state, signal),
-        )
-        .await;
     }
 }
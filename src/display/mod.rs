@@ -1,9 +1,5 @@
-// pub mod game_of_life;
 pub mod matrix_displayer;
-pub mod metaballs;
-// pub mod single;
-pub mod wheel;
-// pub mod wrap;
 pub mod ws2812;
-mod cake;
-mod rgb8;
+
+pub const ROWS: usize = 16;
+pub const COLS: usize = 16;
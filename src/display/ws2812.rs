@@ -1,11 +1,17 @@
 use core::{
+    convert::Infallible,
     ops::{Index, IndexMut},
-    slice,
 };
 
-use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
+use embassy_time::{Duration, Instant, Timer};
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::Rgb888,
+    Pixel,
+};
 use fixed::types::U24F8;
-use fixed_macro::fixed;
+use log::warn;
 
 use embassy_rp::{
     clocks,
@@ -15,12 +21,38 @@ use embassy_rp::{
     Peripheral, PeripheralRef,
 };
 
-use super::rgb8::RGB8;
+use matrix_display::{
+    correction::ColourCorrection,
+    frame_buffer::{Frame, FrameBuffer},
+    graphics, pio_program,
+    pixel_format::PixelFormat,
+    pixel_mapper::PixelMapper,
+    power::{PowerBudget, PowerReport},
+    rgb8::RGB8,
+    timing::LedTiming,
+};
+
+/// Words that can still be on their way out when the DMA finishes: a full TX FIFO, joined to 8
+/// deep, and the one in the output shift register
+const QUEUED_WORDS: u64 = 9;
 
 pub struct Ws2812<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize> {
     dma: PeripheralRef<'d, AnyChannel>,
     sm: StateMachine<'d, P, S>,
+    cfg: Config<'d, P>,
+    /// The colours the effects drew, in the order they are sent down the LED chain
     colours: [[RGB8; COLS]; ROWS],
+    /// The corrected colours that actually go out to the LEDs
+    output: [[RGB8; COLS]; ROWS],
+    /// The output colours packed in the LEDs' channel order, ready for the PIO
+    words: [[u32; COLS]; ROWS],
+    mapper: PixelMapper,
+    correction: ColourCorrection,
+    power: PowerBudget,
+    format: PixelFormat,
+    timing: LedTiming,
+    /// When the LEDs will have latched the last frame and are ready for the next
+    latched_at: Instant,
 }
 
 impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize>
@@ -31,37 +63,23 @@ impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize>
         mut sm: StateMachine<'d, P, S>,
         dma: impl Peripheral<P = impl Channel> + 'd,
         pin: impl PioPin,
+        timing: LedTiming,
     ) -> Self {
         into_ref!(dma);
 
         // Setup sm0
 
         // prepare the PIO program
-        let side_set = pio::SideSet::new(false, 1, false);
-        let mut a: pio::Assembler<32> = pio::Assembler::new_with_side_set(side_set);
-
-        const T1: u8 = 2; // start bit
-        const T2: u8 = 5; // data bit
-        const T3: u8 = 3; // stop bit
-        const CYCLES_PER_BIT: u32 = (T1 + T2 + T3) as u32;
-
-        let mut wrap_target = a.label();
-        let mut wrap_source = a.label();
-        let mut do_zero = a.label();
-        a.set_with_side_set(pio::SetDestination::PINDIRS, 1, 0);
-        a.bind(&mut wrap_target);
-        // Do stop bit
-        a.out_with_delay_and_side_set(pio::OutDestination::X, 1, T3 - 1, 0);
-        // Do start bit
-        a.jmp_with_delay_and_side_set(pio::JmpCondition::XIsZero, &mut do_zero, T1 - 1, 1);
-        // Do data bit = 1
-        a.jmp_with_delay_and_side_set(pio::JmpCondition::Always, &mut wrap_target, T2 - 1, 1);
-        a.bind(&mut do_zero);
-        // Do data bit = 0
-        a.nop_with_delay_and_side_set(T2 - 1, 0);
-        a.bind(&mut wrap_source);
-
-        let prg = a.assemble_with_wrap(wrap_source, wrap_target);
+        let clock_freq = clocks::clk_sys_freq();
+        let (timing, pio_timing) = match timing.pio_timing(clock_freq) {
+            Some(pio_timing) => (timing, pio_timing),
+            None => {
+                warn!("LED timing {} can't be reached, using the default", timing);
+                let default = LedTiming::DEFAULT;
+                (default, default.pio_timing(clock_freq).unwrap())
+            }
+        };
+        let prg = pio_program::ws2812(&pio_timing);
         let mut cfg = Config::default();
 
         // Pin config
@@ -71,18 +89,14 @@ impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize>
 
         cfg.use_program(&pio.load_program(&prg), &[&out_pin]);
 
-        // Clock config, measured in kHz to avoid overflows
-        // TODO CLOCK_FREQ should come from embassy_rp
-        let clock_freq = U24F8::from_num(clocks::clk_sys_freq() / 1000);
-        let ws2812_freq = fixed!(800: U24F8);
-        let bit_freq = ws2812_freq * CYCLES_PER_BIT;
-        cfg.clock_divider = clock_freq / bit_freq;
+        // Clock config
+        cfg.clock_divider = U24F8::from_bits(pio_timing.divider);
 
         // FIFO config
         cfg.fifo_join = FifoJoin::TxOnly;
         cfg.shift_out = ShiftConfig {
             auto_fill: true,
-            threshold: 24,
+            threshold: PixelFormat::DEFAULT.bits(),
             direction: embassy_rp::pio::ShiftDirection::Left,
         };
 
@@ -92,20 +106,103 @@ impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize>
         Self {
             dma: dma.map_into(),
             sm,
+            cfg,
             colours: [[RGB8::default(); COLS]; ROWS],
+            output: [[RGB8::default(); COLS]; ROWS],
+            words: [[0; COLS]; ROWS],
+            mapper: PixelMapper::DEFAULT,
+            correction: ColourCorrection::default(),
+            power: PowerBudget::DEFAULT,
+            format: PixelFormat::DEFAULT,
+            timing,
+            latched_at: Instant::now(),
+        }
+    }
+
+    /// Change the current budget. Budgets below the draw of the LEDs when they're off are
+    /// ignored.
+    pub fn set_power_budget(&mut self, power: PowerBudget) -> Result<(), ()> {
+        if !power.fits(ROWS * COLS) {
+            return Err(());
+        }
+        self.power = power;
+        Ok(())
+    }
+
+    /// Set the gamma (in tenths) and global brightness applied on the way out to the LEDs
+    pub fn set_correction(&mut self, gamma: u8, brightness: u8) {
+        self.correction.set(gamma, brightness);
+    }
+
+    /// The timing the LEDs are driven with, which is fixed when the PIO program is loaded
+    pub fn timing(&self) -> LedTiming {
+        self.timing
+    }
+
+    /// Change the channel order and number of channels sent to each LED
+    pub fn set_format(&mut self, format: PixelFormat) {
+        if format.bits() != self.format.bits() {
+            self.cfg.shift_out.threshold = format.bits();
+            self.sm.set_enable(false);
+            self.sm.set_config(&self.cfg);
+            self.sm.set_enable(true);
         }
+        self.format = format;
     }
 
-    pub async fn write(&mut self) {
+    pub fn mapper(&self) -> PixelMapper {
+        self.mapper
+    }
+
+    /// Change how (row, col) positions map onto the LED chain, keeping the picture as it is.
+    /// Mappings that don't fit the display are ignored.
+    pub fn set_mapper(&mut self, mapper: PixelMapper) -> Result<(), ()> {
+        if !mapper.fits(ROWS, COLS) {
+            return Err(());
+        }
+        let mut frame = Frame::<ROWS, COLS>::new();
+        self.pixels().for_each(|(pos, p)| frame[pos] = p);
+        self.mapper = mapper;
+        frame.pixels().for_each(|(pos, p)| self[pos] = p);
+        Ok(())
+    }
+
+    fn chain_position(&self, row: usize, col: usize) -> (usize, usize) {
+        let i = self.mapper.index::<ROWS, COLS>(row, col);
+        (i / COLS, i % COLS)
+    }
+
+    /// Send the colours out to the LEDs, returning the estimated current draw. `overlay` draws
+    /// over a copy of the colours, for things like notifications that mustn't end up in the
+    /// effects' buffer.
+    pub async fn write(&mut self, overlay: impl FnOnce(&mut Frame<ROWS, COLS>)) -> PowerReport {
+        let mut frame = Frame::<ROWS, COLS>::new();
+        self.pixels().for_each(|(pos, p)| frame[pos] = p);
+        overlay(&mut frame);
+        for ((row, col), colour) in frame.pixels() {
+            let (row, col) = self.chain_position(row, col);
+            self.output[row][col] = self.format.convert(self.correction.apply(colour));
+        }
+        let report = self.power.limit(self.output.as_flattened_mut());
+        self.words
+            .iter_mut()
+            .flatten()
+            .zip(self.output.iter().flatten())
+            .for_each(|(word, &colour)| *word = self.format.encode(colour));
+
+        // Hold the line low until the LEDs have latched the last frame
+        Timer::at(self.latched_at).await;
+
         // DMA transfer
         self.sm
             .tx()
-            .dma_push(self.dma.reborrow(), unsafe {
-                let d: &[u32] =
-                    slice::from_raw_parts(self.colours.as_ptr() as *const u32, ROWS * COLS);
-                d
-            })
+            .dma_push(self.dma.reborrow(), self.words.as_flattened())
             .await;
+
+        let drain_ns = QUEUED_WORDS * self.format.bits() as u64 * self.timing.bit_ns as u64;
+        self.latched_at = Instant::now()
+            + Duration::from_micros(drain_ns.div_ceil(1000) + self.timing.reset_us as u64);
+        report
     }
 }
 
@@ -115,13 +212,8 @@ impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize> Inde
     type Output = RGB8;
 
     fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
-        if row & 1 == 0 {
-            // we are on an even row, reverse direction of column
-            &self.colours[row][COLS - 1 - col]
-        } else {
-            // we are on an odd row, keep direction of column
-            &self.colours[row][col]
-        }
+        let (r, c) = self.chain_position(row, col);
+        &self.colours[r][c]
     }
 }
 
@@ -129,16 +221,40 @@ impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize> Inde
     for Ws2812<'d, P, S, ROWS, COLS>
 {
     fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
-        if row & 1 == 0 {
-            // we are on an even row, reverse direction of column
-            self.colours
-                .get_mut(row)
-                .unwrap()
-                .get_mut(COLS - 1 - col)
-                .unwrap()
-        } else {
-            // we are on an odd row, keep direction of column
-            self.colours.get_mut(row).unwrap().get_mut(col).unwrap()
-        }
+        let (r, c) = self.chain_position(row, col);
+        &mut self.colours[r][c]
+    }
+}
+
+impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize> FrameBuffer<ROWS, COLS>
+    for Ws2812<'d, P, S, ROWS, COLS>
+{
+}
+
+impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize> OriginDimensions
+    for Ws2812<'d, P, S, ROWS, COLS>
+{
+    fn size(&self) -> Size {
+        graphics::size::<ROWS, COLS>()
+    }
+}
+
+impl<'d, P: Instance, const S: usize, const ROWS: usize, const COLS: usize> DrawTarget
+    for Ws2812<'d, P, S, ROWS, COLS>
+{
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        graphics::draw_pixels(self, pixels);
+        Ok(())
+    }
+
+    fn clear(&mut self, colour: Self::Color) -> Result<(), Self::Error> {
+        self.fill(RGB8::from(colour));
+        Ok(())
     }
 }
@@ -4,19 +4,18 @@
 
 mod display;
 mod network;
+mod storage;
 mod web;
 
 use crate::display::matrix_displayer::matrix_task;
+use crate::storage::{load_settings, storage_task, SettingsFlash, SETTINGS_CHANGED};
 use crate::web::start_server;
 use crate::web::WEB_TASK_POOL_SIZE;
 
 use defmt as _;
 use defmt_rtt as _;
-use display::matrix_displayer::Displays;
-use display::metaballs::Metaballs;
+use embassy_rp::flash::Flash;
 use embassy_rp::pio::Pio;
-use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
-use embassy_sync::signal::Signal;
 use panic_probe as _;
 
 use crate::network::set_up_network_stack;
@@ -33,15 +32,16 @@ async fn logger_task(usb: embassy_rp::peripherals::USB) {
     embassy_usb_logger::run!(1024, log::LevelFilter::Info, driver);
 }
 
-static MATRIX_DISPLAY_SIGNAL: Signal<CriticalSectionRawMutex, Displays> = Signal::new();
-
 #[embassy_executor::main]
 async fn main(spawner: embassy_executor::Spawner) {
-    MATRIX_DISPLAY_SIGNAL.signal(Displays::Metaballs(Metaballs::new()));
     let p = embassy_rp::init(Default::default());
 
     spawner.must_spawn(logger_task(p.USB));
 
+    let mut flash: SettingsFlash = Flash::new_blocking(p.FLASH);
+    let settings = load_settings(&mut flash);
+    spawner.must_spawn(storage_task(flash));
+
     let (_, stack) = set_up_network_stack(
         &spawner, p.PIN_23, p.PIN_25, p.PIO0, p.PIN_24, p.PIN_29, p.DMA_CH0,
     )
@@ -54,6 +54,7 @@ async fn main(spawner: embassy_executor::Spawner) {
         pio_led,
         p.DMA_CH1,
         p.PIN_16,
-        &MATRIX_DISPLAY_SIGNAL,
+        settings,
+        &SETTINGS_CHANGED,
     ));
 }
@@ -0,0 +1,78 @@
+use core::cell::RefCell;
+
+use embassy_rp::flash::{Blocking, Flash, ERASE_SIZE};
+use embassy_rp::peripherals::FLASH;
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_sync::signal::Signal;
+use embassy_time::{with_timeout, Duration};
+use log::{info, warn};
+use matrix_display::settings::{Settings, SETTINGS_LEN};
+
+pub const FLASH_SIZE: usize = 2 * 1024 * 1024;
+
+/// The settings live in the last sector of flash, which memory.x keeps clear of the program
+const SETTINGS_OFFSET: u32 = (FLASH_SIZE - ERASE_SIZE) as u32;
+
+pub type SettingsFlash = Flash<'static, FLASH, Blocking, FLASH_SIZE>;
+
+/// The settings currently in use. Change them with [`update_settings`] so they get saved.
+pub static SETTINGS: Mutex<CriticalSectionRawMutex, RefCell<Settings>> =
+    Mutex::new(RefCell::new(Settings::new()));
+
+/// Fires with the new settings whenever they change, for the matrix task to pick up
+pub static SETTINGS_CHANGED: Signal<CriticalSectionRawMutex, Settings> = Signal::new();
+
+static SAVE_SIGNAL: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+/// How long the settings have to go unchanged before they are saved, so dragging a slider on the
+/// web page erases the flash once rather than at every step
+const SAVE_DELAY: Duration = Duration::from_secs(3);
+
+/// Read the saved settings into [`SETTINGS`], keeping the defaults if there are none
+pub fn load_settings(flash: &mut SettingsFlash) -> Settings {
+    let mut buf = [0; SETTINGS_LEN];
+    let settings = match flash.blocking_read(SETTINGS_OFFSET, &mut buf) {
+        Ok(()) => Settings::decode(&buf),
+        Err(e) => {
+            warn!("Couldn't read settings: {:?}", e);
+            Settings::new()
+        }
+    };
+    SETTINGS.lock(|s| *s.borrow_mut() = settings.clone());
+    settings
+}
+
+/// Change the current settings, apply them and save them to flash once they stop changing
+pub fn update_settings(f: impl FnOnce(&mut Settings)) {
+    let settings = SETTINGS.lock(|s| {
+        let mut s = s.borrow_mut();
+        f(&mut s);
+        s.clone()
+    });
+    SETTINGS_CHANGED.signal(settings);
+    SAVE_SIGNAL.signal(());
+}
+
+/// Change the current settings and save them once they stop changing, for a change the matrix
+/// task has already applied
+pub fn save_settings(f: impl FnOnce(&mut Settings)) {
+    SETTINGS.lock(|s| f(&mut s.borrow_mut()));
+    SAVE_SIGNAL.signal(());
+}
+
+#[embassy_executor::task]
+pub async fn storage_task(mut flash: SettingsFlash) {
+    loop {
+        SAVE_SIGNAL.wait().await;
+        // each change starts the wait again
+        while with_timeout(SAVE_DELAY, SAVE_SIGNAL.wait()).await.is_ok() {}
+        let buf = SETTINGS.lock(|s| s.borrow().encode());
+        let result = flash
+            .blocking_erase(SETTINGS_OFFSET, SETTINGS_OFFSET + ERASE_SIZE as u32)
+            .and_then(|()| flash.blocking_write(SETTINGS_OFFSET, &buf));
+        match result {
+            Ok(()) => info!("Saved settings"),
+            Err(e) => warn!("Couldn't save settings: {:?}", e),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use core::fmt::Write;
+use core::fmt::{self, Write};
 use core::str::from_utf8_unchecked;
 
 use cyw43::{Control, NetDriver};
@@ -13,8 +13,25 @@ use picoserve::{
 };
 use static_cell::make_static;
 
-use crate::display::matrix_displayer::Displays;
-use crate::MATRIX_DISPLAY_SIGNAL;
+use matrix_display::compositor::Stack as LayerStack;
+use matrix_display::marquee::MAX_MESSAGE;
+use matrix_display::matrix_displayer::{Displays, EFFECTS};
+use matrix_display::notification::Notification;
+use matrix_display::params::{ParamKind, ParamSpec, ParamValue};
+use matrix_display::pixel_format::PixelFormat;
+use matrix_display::pixel_mapper::PixelMapper;
+use matrix_display::playlist::Playlist;
+use matrix_display::power::PowerBudget;
+use matrix_display::timing::LedTiming;
+use matrix_display::transition::Transition;
+use matrix_display::zones::Layout;
+
+use crate::display::matrix_displayer::{
+    send_command, Command, CommandError, PlaylistCommand, CURRENT_LAYERS, CURRENT_PARAMS,
+    CURRENT_SEED, PLAYLIST_STATUS, POWER_REPORT,
+};
+use crate::display::{COLS, ROWS};
+use crate::storage::{save_settings, update_settings, SETTINGS};
 
 pub const WEB_TASK_POOL_SIZE: usize = 3;
 
@@ -81,6 +98,141 @@ async fn web_task(
     }
 }
 
+type CommandResponse = Result<response::Redirect, (response::StatusCode, &'static str)>;
+
+/// Send a command to the matrix task, going back to the page once it has been applied or saying
+/// why it couldn't be
+async fn command(command: Command) -> CommandResponse {
+    match send_command(command).await {
+        Ok(()) => Ok(response::Redirect::to("/")),
+        Err(e) => {
+            log::warn!("Command failed: {}", e);
+            let status = match e {
+                CommandError::NotRunning => response::status::CONFLICT,
+                CommandError::InvalidValue | CommandError::DoesNotFit => {
+                    response::status::BAD_REQUEST
+                }
+                CommandError::QueueFull | CommandError::Timeout => {
+                    response::status::SERVICE_UNAVAILABLE
+                }
+            };
+            Err((status, "Couldn't apply that\n"))
+        }
+    }
+}
+
+/// Switch to the display, with the given transition or else the default one from the settings.
+/// Picking a display by hand stops the playlist.
+async fn run(display: Option<Displays>, transition: Option<Transition>) -> CommandResponse {
+    let Some(d) = display else {
+        return Err((response::status::NOT_FOUND, "No such display\n"));
+    };
+    if SETTINGS.lock(|s| s.borrow().playlist.enabled) {
+        update_settings(|s| s.playlist.enabled = false);
+    }
+    command(Command::Run(d, transition)).await
+}
+
+fn set_playlist_enabled(enabled: bool) -> response::Redirect {
+    update_settings(|s| s.playlist.enabled = enabled);
+    response::Redirect::to("/")
+}
+
+/// Change a parameter of the running display, if the effect has one with that name and the value
+/// fits it
+async fn set_param(id: usize, name: &str, value: &str) -> CommandResponse {
+    let param = EFFECTS.get(id).and_then(|effect| effect.param(name));
+    match param.map(|(index, spec)| (index, spec.parse(value))) {
+        Some((index, Ok(value))) => command(Command::SetParam(id, index, value)).await,
+        Some((_, Err(_))) => Err((response::status::BAD_REQUEST, "Invalid value\n")),
+        None => Err((response::status::NOT_FOUND, "No such parameter\n")),
+    }
+}
+
+/// An input for the parameter that sets it as soon as it changes, starting at its default
+fn write_param_control(s: &mut impl Write, id: usize, spec: &ParamSpec) -> fmt::Result {
+    let on_change = |value| OnChange {
+        id,
+        name: spec.name,
+        value,
+    };
+    write!(s, "<label>{} ", spec.name)?;
+    let default = spec.show(spec.default);
+    match spec.kind {
+        ParamKind::Int { min, max } => write!(
+            s,
+            "<input type=\"range\" min=\"{}\" max=\"{}\" value=\"{}\" {}>",
+            min,
+            max,
+            default,
+            on_change("this.value")
+        )?,
+        ParamKind::Float { min, max } => write!(
+            s,
+            "<input type=\"range\" min=\"{}\" max=\"{}\" step=\"{}\" value=\"{}\" {}>",
+            min,
+            max,
+            (max - min) / 100.0,
+            default,
+            on_change("this.value")
+        )?,
+        ParamKind::Colour => write!(
+            s,
+            "<input type=\"color\" value=\"#{}\" {}>",
+            default,
+            on_change("this.value.slice(1)")
+        )?,
+        ParamKind::Bool => write!(
+            s,
+            "<input type=\"checkbox\"{} {}>",
+            if spec.default == ParamValue::Bool(true) {
+                " checked"
+            } else {
+                ""
+            },
+            on_change("this.checked")
+        )?,
+        ParamKind::Enum(options) => {
+            write!(s, "<select {}>", on_change("this.value"))?;
+            for (i, option) in options.iter().enumerate() {
+                let selected = spec.default == ParamValue::Enum(i as u8);
+                write!(
+                    s,
+                    "<option{}>{}</option>",
+                    if selected { " selected" } else { "" },
+                    option
+                )?;
+            }
+            write!(s, "</select>")?;
+        }
+        ParamKind::Text => write!(
+            s,
+            "<input value=\"{}\" size=\"12\" {}>",
+            default,
+            on_change("encodeURIComponent(this.value)")
+        )?,
+    }
+    write!(s, "</label> ")
+}
+
+/// The `onchange` attribute that sends the input's value, given as a JavaScript expression, to
+/// param/{id}/{name}/
+struct OnChange {
+    id: usize,
+    name: &'static str,
+    value: &'static str,
+}
+
+impl fmt::Display for OnChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "onchange=\"location.href='param/{}/{}/'+{}\"",
+            self.id, self.name, self.value
+        )
+    }
+}
+
 fn make_app(main_page: &'static str) -> picoserve::Router<AppRouter> {
     picoserve::Router::new()
         .route(
@@ -89,18 +241,295 @@ fn make_app(main_page: &'static str) -> picoserve::Router<AppRouter> {
         )
         .route(
             ("/run", parse_path_segment()),
-            get(|p: usize| async move {
-                match Displays::try_from(p) {
-                    Ok(d) => MATRIX_DISPLAY_SIGNAL.signal(d),
-                    Err(_) => (),
+            get(|p: usize| async move { run(Displays::try_from(p).ok(), None).await }),
+        )
+        .route(
+            ("/run", parse_path_segment(), parse_path_segment()),
+            get(|(p, seed): (usize, u64)| async move {
+                run(Displays::with_seed(p, seed), None).await
+            }),
+        )
+        .route(
+            ("/run", parse_path_segment(), parse_path_segment()),
+            get(|(p, transition): (usize, Transition)| async move {
+                run(Displays::try_from(p).ok(), Some(transition)).await
+            }),
+        )
+        .route(
+            (
+                "/run",
+                parse_path_segment(),
+                parse_path_segment(),
+                parse_path_segment(),
+            ),
+            get(
+                |(p, seed, transition): (usize, u64, Transition)| async move {
+                    run(Displays::with_seed(p, seed), Some(transition)).await
+                },
+            ),
+        )
+        .route("/pause", get(|| async move { command(Command::Pause).await }))
+        .route("/resume", get(|| async move { command(Command::Resume).await }))
+        .route("/step", get(|| async move { command(Command::Step).await }))
+        .route(
+            "/playlist",
+            get(|| async move {
+                let mut s: String<1200> = String::new();
+                let playlist = SETTINGS.lock(|s| s.borrow().playlist.clone());
+                let status = PLAYLIST_STATUS.lock(|s| s.get());
+                write!(s, "{} ({})", playlist, status).unwrap();
+                s
+            }),
+        )
+        .route(
+            "/playlist/start",
+            get(|| async move { set_playlist_enabled(true) }),
+        )
+        .route(
+            "/playlist/stop",
+            get(|| async move { set_playlist_enabled(false) }),
+        )
+        .route(
+            "/playlist/next",
+            get(|| async move { command(Command::Playlist(PlaylistCommand::Next)).await }),
+        )
+        .route(
+            "/playlist/previous",
+            get(|| async move { command(Command::Playlist(PlaylistCommand::Previous)).await }),
+        )
+        .route(
+            "/playlist/pause",
+            get(|| async move { command(Command::Playlist(PlaylistCommand::Pause)).await }),
+        )
+        .route(
+            "/playlist/resume",
+            get(|| async move { command(Command::Playlist(PlaylistCommand::Resume)).await }),
+        )
+        .route(
+            ("/playlist", parse_path_segment()),
+            get(|playlist: Playlist| async move {
+                update_settings(|s| {
+                    s.playlist = Playlist {
+                        enabled: s.playlist.enabled,
+                        ..playlist
+                    }
+                });
+                response::Redirect::to("/")
+            }),
+        )
+        .route(
+            "/transition",
+            get(|| async move {
+                let mut s: String<24> = String::new();
+                write!(s, "{}", SETTINGS.lock(|s| s.borrow().transition)).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/transition", parse_path_segment()),
+            get(|transition: Transition| async move {
+                update_settings(|s| s.transition = transition);
+                response::Redirect::to("/")
+            }),
+        )
+        .route(
+            "/effects",
+            get(|| async move {
+                let mut s: String<1024> = String::new();
+                for effect in EFFECTS {
+                    writeln!(
+                        s,
+                        "{},{},{},{}",
+                        effect.id, effect.name, effect.frame_spacing, effect.description
+                    )
+                    .unwrap();
                 }
+                s
+            }),
+        )
+        .route(
+            "/params",
+            get(|| async move {
+                let mut s: String<256> = String::new();
+                let (id, values) = CURRENT_PARAMS.lock(|p| p.borrow().clone());
+                let effect = &EFFECTS[id];
+                writeln!(s, "{}", effect.name).unwrap();
+                for (spec, value) in effect.params.iter().zip(values) {
+                    writeln!(s, "{}={}", spec.name, spec.show(value)).unwrap();
+                }
+                s
+            }),
+        )
+        .route(
+            (
+                "/param",
+                parse_path_segment(),
+                parse_path_segment(),
+                parse_path_segment(),
+            ),
+            get(
+                |(id, name, value): (usize, String<16>, String<32>)| async move {
+                    set_param(id, &name, &value).await
+                },
+            ),
+        )
+        .route(
+            ("/text", parse_path_segment()),
+            get(|message: String<MAX_MESSAGE>| async move {
+                command(Command::Text(message)).await
+            }),
+        )
+        .route(
+            "/layers",
+            get(|| async move {
+                let mut s: String<96> = String::new();
+                write!(s, "{}", CURRENT_LAYERS.lock(|l| l.borrow().clone())).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/layers", parse_path_segment()),
+            get(|stack: LayerStack| async move { command(Command::Layers(stack)).await }),
+        )
+        .route(
+            "/zones",
+            get(|| async move {
+                let mut s: String<96> = String::new();
+                write!(s, "{}", SETTINGS.lock(|s| s.borrow().zones.clone())).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/zones", parse_path_segment()),
+            get(|zones: Layout| async move {
+                if !zones.fits(ROWS, COLS) {
+                    return Err((response::status::BAD_REQUEST, "Those zones don't fit\n"));
+                }
+                update_settings(|s| s.zones = zones);
+                Ok(response::Redirect::to("/"))
+            }),
+        )
+        .route(
+            "/notify/clear",
+            get(|| async move { command(Command::ClearNotifications).await }),
+        )
+        .route(
+            ("/notify", parse_path_segment()),
+            get(|notification: Notification| async move {
+                command(Command::Notify(notification)).await
+            }),
+        )
+        .route(
+            "/seed",
+            get(|| async move {
+                let mut s: String<24> = String::new();
+                match CURRENT_SEED.lock(|seed| seed.get()) {
+                    Some(seed) => write!(s, "{}", seed).unwrap(),
+                    None => write!(s, "none").unwrap(),
+                }
+                s
+            }),
+        )
+        .route(
+            "/mapping",
+            get(|| async move {
+                let mut s: String<64> = String::new();
+                write!(s, "{}", SETTINGS.lock(|s| s.borrow().mapper)).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/mapping", parse_path_segment()),
+            get(|mapper: PixelMapper| async move {
+                let response = command(Command::SetMapper(mapper)).await;
+                if response.is_ok() {
+                    save_settings(|s| s.mapper = mapper);
+                }
+                response
+            }),
+        )
+        .route(
+            "/brightness",
+            get(|| async move {
+                let mut s: String<8> = String::new();
+                write!(s, "{}", SETTINGS.lock(|s| s.borrow().brightness)).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/brightness", parse_path_segment()),
+            get(|brightness: u8| async move {
+                let response = command(Command::SetBrightness(brightness)).await;
+                if response.is_ok() {
+                    save_settings(|s| s.brightness = brightness);
+                }
+                response
+            }),
+        )
+        .route(
+            "/power",
+            get(|| async move {
+                let mut s: String<96> = String::new();
+                let budget = SETTINGS.lock(|s| s.borrow().power);
+                let report = POWER_REPORT.lock(|r| r.get());
+                write!(s, "{} of {} mA budget", report, budget.budget_ma).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/power", parse_path_segment()),
+            get(|power: PowerBudget| async move {
+                if !power.fits(ROWS * COLS) {
+                    return Err((
+                        response::status::BAD_REQUEST,
+                        "That budget is less than the LEDs draw when they're off\n",
+                    ));
+                }
+                update_settings(|s| s.power = power);
+                Ok(response::Redirect::to("/"))
+            }),
+        )
+        .route(
+            "/format",
+            get(|| async move {
+                let mut s: String<8> = String::new();
+                write!(s, "{}", SETTINGS.lock(|s| s.borrow().format)).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/format", parse_path_segment()),
+            get(|format: PixelFormat| async move {
+                update_settings(|s| s.format = format);
+                response::Redirect::to("/")
+            }),
+        )
+        .route(
+            "/timing",
+            get(|| async move {
+                let mut s: String<32> = String::new();
+                write!(s, "{}", SETTINGS.lock(|s| s.borrow().timing)).unwrap();
+                s
+            }),
+        )
+        .route(
+            ("/timing", parse_path_segment()),
+            get(|timing: LedTiming| async move {
+                update_settings(|s| s.timing = timing);
+                response::Redirect::to("/")
+            }),
+        )
+        .route(
+            ("/gamma", parse_path_segment()),
+            get(|gamma: u8| async move {
+                update_settings(|s| s.gamma = gamma);
                 response::Redirect::to("/")
             }),
         )
 }
 
 pub async fn start_server(spawner: &Spawner, stack: &'static Stack<NetDriver<'static>>) {
-    let mut s: &'static mut String<4000> = make_static!(String::new());
+    let mut s: &'static mut String<10240> = make_static!(String::new());
     write!(
         s,
         "<!DOCTYPE html>
@@ -110,14 +539,86 @@ pub async fn start_server(spawner: &Spawner, stack: &'static Stack<NetDriver<'st
 <ul>"
     )
     .unwrap();
-    for (i, n) in [(0, "Wheel"), (1, "Metaballs"), (2, "Cake")] {
-        write!(s, "<li><a href=\"run/{}\">{}</a></li>", i, n).unwrap();
+    for effect in EFFECTS {
+        write!(
+            s,
+            "<li><a href=\"run/{}\">{}</a>: {}<br>",
+            effect.id, effect.name, effect.description
+        )
+        .unwrap();
+        for spec in effect.params {
+            write_param_control(s, effect.id, spec).unwrap();
+        }
+        write!(s, "</li>").unwrap();
     }
     write!(
         s,
         "</ul>
+<p>The <a href=\"effects\">effects</a> are also listed as id,name,frame ms,description.
+The <a href=\"seed\">seed</a> of the running display can be replayed with run/{{id}}/{{seed}}.
+The running display's <a href=\"params\">parameters</a> are changed with param/{{id}}/{{name}}/{{value}},
+giving colours as rrggbb. Automaton rules are B{{counts}}/S{{counts}}[/C{{states}}][V], rule{{n}} or a name like life.</p>
+<p><a href=\"pause\">Pause</a> <a href=\"step\">Step</a> <a href=\"resume\">Resume</a> freezes the matrix,
+moves it on a frame at a time and starts it again.</p>
+<h2>Text</h2>
+<form onsubmit=\"location.href='text/'+encodeURIComponent(this.m.value);return false\">
+<input name=\"m\" maxlength=\"{}\"> <input type=\"submit\" value=\"Show\">
+</form>
+<p>Shows a message of up to {} characters on the Text display, switching to it if it isn't running.
+The message can also be sent with text/{{message}}, URL encoded.</p>
+<h2>Layers</h2>
+<p>Other displays can run as <a href=\"layers\">layers</a> over the running one, set bottom first with
+layers/{{id}}[:{{blend}}[:{{opacity}}]],... The blends are normal, add, multiply, screen and max, and the
+opacity goes up to 255. Black is see-through on a normal layer. <a href=\"layers/none\">None</a></p>
+<h2>Zones</h2>
+<p>The matrix can be split into <a href=\"zones\">zones</a> each running its own display, set with
+zones/{{id}}:{{row}}:{{col}}:{{rows}}x{{cols}},... Zones are up to 16 pixels each way and have to fit on the matrix.
+Each shows the top left of its display's 16x16 frame, so centred things like text are cut off in small zones.
+<a href=\"zones/1:0:0:8x16,0:8:0:8x16\">Metaballs over the wheel</a> <a href=\"zones/none\">None</a></p>
+<h2>Notifications</h2>
+<p>notify/{{style}},{{seconds}},{{priority}},{{rrggbb}}[,{{extra}}] shows an alert over the running display.
+The styles are border, icon with alert, tick, cross or heart, and text with a message.
+Higher priorities go first. <a href=\"notify/clear\">Clear</a></p>
+<h2>Transitions</h2>
+<p>Displays switch with the default <a href=\"transition\">transition</a>, set with
+transition/{{kind}},{{duration ms}}, or with another one given as run/{{id}}[/{{seed}}]/{{kind}},{{duration ms}}.
+The kinds are cut, crossfade, wipe-left, wipe-right, dissolve, slide and iris.</p>
+<h2>Playlist</h2>
+<p>The <a href=\"playlist\">playlist</a> shows each display in turn, set with
+playlist/[shuffle,]{{id}}:{{seconds}}[:{{seed}}][:{{name}}={{value}};...],... The params are set as each entry starts.
+Picking a display by hand stops it.</p>
+<p><a href=\"playlist/start\">Start</a> <a href=\"playlist/stop\">Stop</a>
+<a href=\"playlist/previous\">Previous</a> <a href=\"playlist/next\">Next</a>
+<a href=\"playlist/pause\">Pause</a> <a href=\"playlist/resume\">Resume</a></p>
+<h2>Brightness</h2>
+<form onsubmit=\"location.href='brightness/'+this.b.value;return false\">
+<input type=\"range\" name=\"b\" min=\"0\" max=\"255\" onchange=\"this.form.requestSubmit()\">
+</form>
+<p>Current <a href=\"brightness\">brightness</a>. Gamma is set in tenths with gamma/{{gamma}}, e.g. gamma/22 for 2.2.</p>
+<h2>Power</h2>
+<p>The estimated <a href=\"power\">current draw</a> is kept under a budget, set with
+power/{{budget mA}},{{mA per channel}},{{idle uA per LED}}.</p>
+<h2>Wiring</h2>
+<p>The current <a href=\"mapping\">pixel mapping</a> can be changed with
+mapping/{{wiring}},{{start corner}},{{rotation}},{{mirror}},{{tiles down}}x{{tiles across}}[,{{tile wiring}}].</p>
+<ul>
+<li><a href=\"mapping/serpentine,top-right,0,none,1x1\">Serpentine</a></li>
+<li><a href=\"mapping/progressive,top-left,0,none,1x1\">Progressive</a></li>
+<li><a href=\"mapping/serpentine,top-right,90,none,1x1\">Serpentine, rotated 90&deg;</a></li>
+<li><a href=\"mapping/serpentine,top-right,0,none,2x2,serpentine\">2x2 serpentine panels</a></li>
+</ul>
+<p>The LEDs' <a href=\"format\">colour order</a> can be changed with format/{{order}}, adding a w for RGBW LEDs.</p>
+<ul>
+<li><a href=\"format/grb\">WS2812B</a></li>
+<li><a href=\"format/rgb\">WS2811</a></li>
+<li><a href=\"format/grbw\">SK6812 RGBW</a></li>
+</ul>
+<p>The LEDs' bit <a href=\"timing\">timing</a> is one of ws2812b, ws2811 or sk6812, or
+timing/{{0 high ns}},{{1 high ns}},{{bit ns}},{{reset us}}. It takes effect after a restart.</p>
 </body>
-</html> "
+</html> ",
+        MAX_MESSAGE,
+        MAX_MESSAGE
     )
     .unwrap();
     let app = make_static!(make_app(s));